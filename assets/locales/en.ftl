@@ -0,0 +1,92 @@
+game-name = Jophus' Horror
+
+## Main menu
+
+menu-play = PLAY ▶
+menu-setup = SETUP ⚙
+menu-quit = STOP ■
+menu-level = LEVEL
+
+## Pause menu
+
+pause-resume = PLAY ▶
+pause-setup = SETUP ⚙
+pause-gallery = TAPES ▤
+pause-main-menu = STOP ■
+
+## Gallery
+
+gallery = TAPES
+gallery-evidence = EVIDENCE FOUND: { $count }
+gallery-capture = CAPTURE
+gallery-developing = reading tape…
+gallery-nothing = nothing of note
+gallery-empty = nothing recorded yet
+evidence-bloodstain = bloodstain
+evidence-doll = doll
+evidence-grave = grave
+
+## Objectives
+
+objectives = OBJECTIVES
+objective-new = NEW OBJECTIVE: { $objective }
+objective-completed = COMPLETED: { $objective }
+objective-failed = FAILED: { $objective }
+objective-bloodstain = Film the bloodstain
+objective-doll = Film the doll
+objective-yard = Go out to the yard
+objective-grave = Film the grave
+
+## Loading failed
+
+error-no-signal = NO SIGNAL
+error-load-failed = Failed to load assets
+error-retry = REW ◀◀
+error-fallbacks = Continue with fallbacks
+error-quit = STOP ■
+
+## Settings
+
+settings = SETUP
+settings-language = LANGUAGE
+settings-flashing = FLASHING
+flashing-on = on
+flashing-limited = limited
+flashing-off = off
+settings-motion = MOTION
+motion-full = full
+motion-reduced = reduced
+settings-hud = HUD
+hud-normal = normal
+hud-high-contrast = high contrast
+settings-text-size = TEXT SIZE
+settings-colours = COLOURS
+colours-standard = standard
+colours-colour-blind-safe = colour-blind safe
+settings-held-actions = HELD ACTIONS
+held-actions-hold = hold
+held-actions-toggle = toggle
+settings-back = BACK ◀
+
+## In game
+
+hud-play = Play: ▶
+hud-pause = Pause: ▌▌
+hud-rec = REC
+hud-low-battery = BATTERY LOW
+hud-battery-empty = BATTERY EMPTY
+hud-zoom-wide = W
+hud-zoom-tele = T
+hud-tape-left = SP { $time }
+
+## Splash screen
+
+splash-studio = A JOPHUS PRODUCTION
+splash-play = PLAY ▶
+splash-warning = WARNING
+splash-warning-body =
+    This tape was recovered from the house on the hill.
+    It is licensed for private home viewing only.
+    Viewing after dark is strongly discouraged.
+splash-tracking = TRACKING
+splash-failed = Failed to load { $path }: { $reason }
@@ -0,0 +1,92 @@
+game-name = El Horror de Jophus
+
+## Main menu
+
+menu-play = REPRODUCIR ▶
+menu-setup = AJUSTES ⚙
+menu-quit = PARAR ■
+menu-level = NIVEL
+
+## Pause menu
+
+pause-resume = REPRODUCIR ▶
+pause-setup = AJUSTES ⚙
+pause-gallery = CINTAS ▤
+pause-main-menu = PARAR ■
+
+## Gallery
+
+gallery = CINTAS
+gallery-evidence = PRUEBAS HALLADAS: { $count }
+gallery-capture = TOMA
+gallery-developing = leyendo la cinta…
+gallery-nothing = nada destacable
+gallery-empty = aún no hay nada grabado
+evidence-bloodstain = mancha de sangre
+evidence-doll = muñeca
+evidence-grave = tumba
+
+## Objectives
+
+objectives = OBJETIVOS
+objective-new = NUEVO OBJETIVO: { $objective }
+objective-completed = COMPLETADO: { $objective }
+objective-failed = FALLIDO: { $objective }
+objective-bloodstain = Graba la mancha de sangre
+objective-doll = Graba la muñeca
+objective-yard = Sal al patio
+objective-grave = Graba la tumba
+
+## Loading failed
+
+error-no-signal = SIN SEÑAL
+error-load-failed = No se pudieron cargar los recursos
+error-retry = REBOB. ◀◀
+error-fallbacks = Continuar con sustitutos
+error-quit = PARAR ■
+
+## Settings
+
+settings = AJUSTES
+settings-language = IDIOMA
+settings-flashing = DESTELLOS
+flashing-on = activados
+flashing-limited = limitados
+flashing-off = desactivados
+settings-motion = MOVIMIENTO
+motion-full = completo
+motion-reduced = reducido
+settings-hud = HUD
+hud-normal = normal
+hud-high-contrast = alto contraste
+settings-text-size = TAMAÑO DE TEXTO
+settings-colours = COLORES
+colours-standard = estándar
+colours-colour-blind-safe = aptos para daltónicos
+settings-held-actions = ACCIONES MANTENIDAS
+held-actions-hold = mantener
+held-actions-toggle = alternar
+settings-back = VOLVER ◀
+
+## In game
+
+hud-play = Reproducir: ▶
+hud-pause = Pausa: ▌▌
+hud-rec = REC
+hud-low-battery = BATERÍA BAJA
+hud-battery-empty = BATERÍA AGOTADA
+hud-zoom-wide = G
+hud-zoom-tele = T
+hud-tape-left = SP { $time }
+
+## Splash screen
+
+splash-studio = UNA PRODUCCIÓN DE JOPHUS
+splash-play = REPRODUCIR ▶
+splash-warning = ADVERTENCIA
+splash-warning-body =
+    Esta cinta fue recuperada de la casa de la colina.
+    Su licencia se limita al visionado doméstico privado.
+    Se desaconseja encarecidamente verla después del anochecer.
+splash-tracking = TRACKING
+splash-failed = No se pudo cargar { $path }: { $reason }
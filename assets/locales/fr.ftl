@@ -0,0 +1,92 @@
+game-name = L'Horreur de Jophus
+
+## Main menu
+
+menu-play = LECTURE ▶
+menu-setup = RÉGLAGES ⚙
+menu-quit = ARRÊT ■
+menu-level = NIVEAU
+
+## Pause menu
+
+pause-resume = LECTURE ▶
+pause-setup = RÉGLAGES ⚙
+pause-gallery = CASSETTES ▤
+pause-main-menu = ARRÊT ■
+
+## Gallery
+
+gallery = CASSETTES
+gallery-evidence = PREUVES TROUVÉES : { $count }
+gallery-capture = PRISE
+gallery-developing = lecture de la cassette…
+gallery-nothing = rien à signaler
+gallery-empty = rien d'enregistré pour l'instant
+evidence-bloodstain = tache de sang
+evidence-doll = poupée
+evidence-grave = tombe
+
+## Objectives
+
+objectives = OBJECTIFS
+objective-new = NOUVEL OBJECTIF : { $objective }
+objective-completed = TERMINÉ : { $objective }
+objective-failed = ÉCHOUÉ : { $objective }
+objective-bloodstain = Filmer la tache de sang
+objective-doll = Filmer la poupée
+objective-yard = Sortir dans la cour
+objective-grave = Filmer la tombe
+
+## Loading failed
+
+error-no-signal = PAS DE SIGNAL
+error-load-failed = Échec du chargement des ressources
+error-retry = REMBOB. ◀◀
+error-fallbacks = Continuer avec des substituts
+error-quit = ARRÊT ■
+
+## Settings
+
+settings = RÉGLAGES
+settings-language = LANGUE
+settings-flashing = FLASHS
+flashing-on = activés
+flashing-limited = limités
+flashing-off = désactivés
+settings-motion = MOUVEMENT
+motion-full = complet
+motion-reduced = réduit
+settings-hud = HUD
+hud-normal = normal
+hud-high-contrast = contraste élevé
+settings-text-size = TAILLE DU TEXTE
+settings-colours = COULEURS
+colours-standard = standard
+colours-colour-blind-safe = adaptées au daltonisme
+settings-held-actions = ACTIONS MAINTENUES
+held-actions-hold = maintenir
+held-actions-toggle = basculer
+settings-back = RETOUR ◀
+
+## In game
+
+hud-play = Lecture : ▶
+hud-pause = Pause : ▌▌
+hud-rec = REC
+hud-low-battery = BATTERIE FAIBLE
+hud-battery-empty = BATTERIE VIDE
+hud-zoom-wide = G
+hud-zoom-tele = T
+hud-tape-left = SP { $time }
+
+## Splash screen
+
+splash-studio = UNE PRODUCTION JOPHUS
+splash-play = LECTURE ▶
+splash-warning = AVERTISSEMENT
+splash-warning-body =
+    Cette cassette a été retrouvée dans la maison sur la colline.
+    Elle est réservée à un usage privé au sein du foyer.
+    Son visionnage après la tombée de la nuit est fortement déconseillé.
+splash-tracking = ALIGNEMENT
+splash-failed = Échec du chargement de { $path } : { $reason }
@@ -0,0 +1,23 @@
+#import bevy_ui::ui_vertex_output::UiVertexOutput
+
+@group(1) @binding(0) var<uniform> strength: f32;
+@group(1) @binding(1) var<uniform> time: f32;
+@group(1) @binding(2) var<uniform> tint: vec4<f32>;
+
+fn random_vec2(st: vec2<f32>) -> f32 {
+    return fract(sin(dot(st.xy, vec2(12.9898, 78.233))) * 43758.5453);
+}
+
+fn modulo(x: f32, y: f32) -> f32 {
+    return x - y * floor(x / y);
+}
+
+@fragment
+fn fragment(in: UiVertexOutput) -> @location(0) vec4<f32> {
+    // speckles a couple of pixels wide, changing every frame
+    let speckle = random_vec2(floor(in.position.xy / 2.0) + fract(time) * 100.0);
+    // a band of heavier noise rolling down, like the one in vhs.wgsl
+    let band = step(1.9, modulo(-time / 1.5 + in.uv.y, 2.0));
+
+    return vec4<f32>(vec3(speckle) * tint.rgb, strength * (1.0 + band * 2.0));
+}
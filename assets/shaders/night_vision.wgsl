@@ -0,0 +1,9 @@
+#import bevy_ui::ui_vertex_output::UiVertexOutput
+
+@group(1) @binding(0) var<uniform> tint: vec4<f32>;
+
+// multiplied with what's already drawn, see `NightVisionFilter::specialize`
+@fragment
+fn fragment(in: UiVertexOutput) -> @location(0) vec4<f32> {
+    return tint;
+}
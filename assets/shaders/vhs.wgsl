@@ -4,8 +4,10 @@
 #import bevy_pbr::utils
 
 @group(0) @binding(0) var<uniform> view: View;
+@group(2) @binding(100) var<uniform> color: vec4<f32>;
 @group(2) @binding(101) var texture: texture_2d<f32>;
 @group(2) @binding(102) var texture_sampler: sampler;
+@group(2) @binding(103) var<uniform> noise: f32;
 
 #ifndef RANDOM_SCALE
 #ifdef RANDOM_HIGHER_RANGE
@@ -50,9 +52,11 @@ fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
     var colour = textureSample(texture, texture_sampler, uv).xyz;
     let vhs = rolling_distort(uv, -globals.time /2);
     let edges = step(0.005, pow( 1.0*uv.x*uv.y*(1.0-uv.y)*(1.0-uv.x), 1.0 ));
-    colour += mix(1.0, vhs, 1.0)/3.;
+    colour += mix(1.0, vhs, 1.0)/3. * noise;
 
     colour = mix(vec3<f32>(0.0, 0.0, 0.0), colour, edges);
+    // tint of the tape preset
+    colour *= color.rgb;
 
     return vec4<f32>(
         colour,
@@ -0,0 +1,224 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiSettings};
+use leafwing_input_manager::prelude::*;
+
+use crate::localization::{Locale, Localization};
+use crate::navigation::MenuFocus;
+use crate::osd;
+
+/// Text sizes offered in the settings, as a multiple of the default
+pub const TEXT_SCALES: [f32; 4] = [1.0, 1.25, 1.5, 2.0];
+
+/// Keeps [`Accessibility`] settings, every effect they cover reads them directly
+pub struct AccessibilityPlugin;
+
+impl Plugin for AccessibilityPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Accessibility>()
+            .add_systems(Update, scale_egui.run_if(resource_changed::<Accessibility>));
+    }
+}
+
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct Accessibility {
+    pub flashing: Flashing,
+    /// Leave out camera shake, head bob and anything else moving on its own
+    pub reduce_motion: bool,
+    /// Solid backing behind HUD text
+    pub high_contrast: bool,
+    /// Multiplies the size of HUD and menu text, see [`TEXT_SCALES`]
+    pub text_scale: f32,
+    /// Cues that would be red or green use colours told apart with any colour vision
+    pub colour_blind_safe: bool,
+    /// Actions that are normally held down are toggled with a press instead, see [`Latch`]
+    pub hold_to_toggle: bool,
+}
+
+impl Default for Accessibility {
+    fn default() -> Self {
+        Self {
+            flashing: Flashing::On,
+            reduce_motion: false,
+            high_contrast: false,
+            text_scale: 1.0,
+            colour_blind_safe: false,
+            hold_to_toggle: false,
+        }
+    }
+}
+
+/// How much the flashlight, lightning, VHS noise and blinking HUD are allowed to flash
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Flashing {
+    #[default]
+    On,
+    /// Shallower and rarer
+    Limited,
+    Off,
+}
+
+impl Flashing {
+    pub const ALL: [Self; 3] = [Self::On, Self::Limited, Self::Off];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::On => "on",
+            Self::Limited => "limited",
+            Self::Off => "off",
+        }
+    }
+}
+
+impl Accessibility {
+    /// Strength of the VHS shader's rolling noise
+    pub fn vhs_noise(&self) -> f32 {
+        match self.flashing {
+            Flashing::On => 1.0,
+            Flashing::Limited => 0.4,
+            Flashing::Off => 0.0,
+        }
+    }
+
+    /// Colour of warnings and failures
+    pub fn danger(&self) -> Color {
+        if self.colour_blind_safe {
+            // orange from the Okabe-Ito palette, stands out without relying on red
+            Color::srgb_u8(230, 159, 0)
+        } else {
+            Color::srgb(1.0, 0.2, 0.2)
+        }
+    }
+
+    pub fn danger_egui(&self) -> egui::Color32 {
+        let [r, g, b, a] = self.danger().to_srgba().to_u8_array();
+        egui::Color32::from_rgba_unmultiplied(r, g, b, a)
+    }
+}
+
+fn scale_egui(accessibility: Res<Accessibility>, settings: Option<ResMut<EguiSettings>>) {
+    if let Some(mut settings) = settings {
+        settings.scale_factor = accessibility.text_scale;
+    }
+}
+
+/// Lay out every setting with OSD styling, returning `true` once the player goes back
+pub fn settings_ui(
+    ui: &mut egui::Ui,
+    focus: &mut MenuFocus,
+    localization: &Localization,
+    locale: &mut Locale,
+    accessibility: &mut Accessibility,
+) -> bool {
+    let text = |key: &str| localization.text(key);
+    ui.label(osd::text(text("settings"), 64.0));
+
+    ui.add_space(10.0);
+
+    let locales: Vec<_> = localization.locales().collect();
+    let mut language = locales
+        .iter()
+        .position(|(id, _)| *id == locale.0)
+        .unwrap_or_default();
+    let names: Vec<_> = locales.iter().map(|(_, name)| *name).collect();
+    if focus.choice(ui, &text("settings-language"), &mut language, &names) {
+        locale.0 = locales[language].0.to_string();
+    }
+
+    let mut flashing = Flashing::ALL
+        .iter()
+        .position(|flashing| *flashing == accessibility.flashing)
+        .unwrap_or_default();
+    let names = Flashing::ALL.map(|flashing| text(&format!("flashing-{}", flashing.name())));
+    let names = names.each_ref().map(AsRef::as_ref);
+    if focus.choice(ui, &text("settings-flashing"), &mut flashing, &names) {
+        accessibility.flashing = Flashing::ALL[flashing];
+    }
+
+    toggle(
+        ui,
+        focus,
+        localization,
+        "settings-motion",
+        ["motion-full", "motion-reduced"],
+        &mut accessibility.reduce_motion,
+    );
+    toggle(
+        ui,
+        focus,
+        localization,
+        "settings-hud",
+        ["hud-normal", "hud-high-contrast"],
+        &mut accessibility.high_contrast,
+    );
+
+    let mut scale = TEXT_SCALES
+        .iter()
+        .position(|scale| *scale == accessibility.text_scale)
+        .unwrap_or_default();
+    let names = TEXT_SCALES.map(|scale| format!("{}%", scale * 100.0));
+    let names = names.each_ref().map(String::as_str);
+    if focus.choice(ui, &text("settings-text-size"), &mut scale, &names) {
+        accessibility.text_scale = TEXT_SCALES[scale];
+    }
+
+    toggle(
+        ui,
+        focus,
+        localization,
+        "settings-colours",
+        ["colours-standard", "colours-colour-blind-safe"],
+        &mut accessibility.colour_blind_safe,
+    );
+    toggle(
+        ui,
+        focus,
+        localization,
+        "settings-held-actions",
+        ["held-actions-hold", "held-actions-toggle"],
+        &mut accessibility.hold_to_toggle,
+    );
+
+    let back = focus.button(ui, osd::text(text("settings-back"), 24.0));
+    back || focus.back()
+}
+
+// A setting switched between two options, all given as localization keys
+fn toggle(
+    ui: &mut egui::Ui,
+    focus: &mut MenuFocus,
+    localization: &Localization,
+    label: &str,
+    names: [&str; 2],
+    value: &mut bool,
+) {
+    let mut selected = *value as usize;
+    let names = names.map(|name| localization.text(name));
+    let names = names.each_ref().map(AsRef::as_ref);
+    if focus.choice(ui, &localization.text(label), &mut selected, &names) {
+        *value = selected == 1;
+    }
+}
+
+/// Whether an action that is normally held is active, latching on a press instead
+/// when [`Accessibility::hold_to_toggle`] is set
+#[derive(Debug, Default)]
+pub struct Latch(bool);
+
+impl Latch {
+    pub fn active<A: Actionlike>(
+        &mut self,
+        accessibility: &Accessibility,
+        action_state: &ActionState<A>,
+        action: &A,
+    ) -> bool {
+        if accessibility.hold_to_toggle {
+            if action_state.just_pressed(action) {
+                self.0 = !self.0;
+            }
+            self.0
+        } else {
+            self.0 = false;
+            action_state.pressed(action)
+        }
+    }
+}
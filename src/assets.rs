@@ -0,0 +1,250 @@
+use bevy::asset::LoadState;
+use bevy::prelude::*;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{
+    Extent3d, TextureDimension, TextureFormat, TextureViewDescriptor, TextureViewDimension,
+};
+use bevy::utils::HashMap;
+use bevy_asset_loader::prelude::*;
+use bevy_common_assets::ron::RonAssetPlugin;
+use iyes_progress::prelude::*;
+
+use super::GameState;
+use crate::game::level::LevelRegistry;
+
+/// Paths of the assets loaded while in `GameState::Splash`, relative to the asset folder.
+#[derive(Clone, Debug)]
+pub struct AssetPaths {
+    pub intro: String,
+    /// Registry listing every level and the assets it streams in
+    pub levels: String,
+}
+
+impl Default for AssetPaths {
+    fn default() -> Self {
+        Self {
+            intro: "audio/haunting_piano.ogg".into(),
+            levels: "horror.levels.ron".into(),
+        }
+    }
+}
+
+impl AssetPaths {
+    /// Key and path of every asset, grouped by the collection loading it
+    pub fn collections(&self) -> [(&'static str, Vec<(&'static str, &str)>); 2] {
+        [
+            ("Audio", vec![("intro", self.intro.as_str())]),
+            ("Levels", vec![("levels", self.levels.as_str())]),
+        ]
+    }
+}
+
+#[derive(AssetCollection, Resource)]
+pub struct AudioAssets {
+    #[asset(key = "intro")]
+    pub intro: Handle<AudioSource>,
+}
+
+#[derive(AssetCollection, Resource)]
+pub struct LevelAssets {
+    #[asset(key = "levels")]
+    pub registry: Handle<LevelRegistry>,
+}
+
+/// Load progress of every asset collection, updated while in `GameState::Splash`.
+/// Level assets that fail to load in release builds are added under
+/// [`crate::game::level::LEVEL_COLLECTION`].
+#[derive(Resource, Debug, Default)]
+pub struct LoadingProgress {
+    pub collections: Vec<CollectionProgress>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CollectionProgress {
+    pub name: &'static str,
+    pub done: usize,
+    pub total: usize,
+    /// Path and reason of every asset that failed to load
+    pub failures: Vec<(String, String)>,
+}
+
+impl CollectionProgress {
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            return 1.0;
+        }
+        self.done as f32 / self.total as f32
+    }
+}
+
+impl LoadingProgress {
+    pub fn failures(&self) -> impl Iterator<Item = &(String, String)> {
+        self.collections.iter().flat_map(|c| c.failures.iter())
+    }
+}
+
+pub struct AssetsPlugin {
+    pub paths: AssetPaths,
+    /// State to continue to once every collection is loaded
+    pub next_state: GameState,
+}
+
+impl Default for AssetsPlugin {
+    fn default() -> Self {
+        Self {
+            paths: AssetPaths::default(),
+            next_state: GameState::Menu,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct LoadingConfig {
+    pub paths: AssetPaths,
+    pub next_state: GameState,
+}
+
+impl Plugin for AssetsPlugin {
+    fn build(&self, app: &mut App) {
+        let collections = self.paths.collections();
+        let dynamic_assets = collections.iter().flat_map(|(_, assets)| {
+            assets.iter().map(|(key, path)| {
+                (
+                    key.to_string(),
+                    StandardDynamicAsset::File {
+                        path: path.to_string(),
+                    },
+                )
+            })
+        });
+
+        app.add_loading_state(
+            LoadingState::new(GameState::Splash)
+                .on_failure_continue_to_state(GameState::LoadFailed)
+                .add_standard_dynamic_assets(HashMap::from_iter(dynamic_assets))
+                .load_collection::<AudioAssets>()
+                .load_collection::<LevelAssets>(),
+        )
+        .add_plugins(RonAssetPlugin::<LevelRegistry>::new(&["levels.ron"]))
+        // the transition is left to the progress tracker so other plugins can hold the state,
+        // e.g. to show the splash screen for a minimum amount of time
+        .add_plugins(ProgressPlugin::new(GameState::Splash).continue_to(self.next_state.clone()))
+        .insert_resource(LoadingProgress {
+            collections: collections
+                .iter()
+                .map(|(name, assets)| CollectionProgress {
+                    name,
+                    done: 0,
+                    total: assets.len(),
+                    failures: Vec::new(),
+                })
+                .collect(),
+        })
+        .insert_resource(LoadingConfig {
+            paths: self.paths.clone(),
+            next_state: self.next_state.clone(),
+        })
+        .add_systems(
+            Update,
+            track_collections
+                .track_progress()
+                .run_if(in_state(GameState::Splash)),
+        )
+        .add_systems(OnExit(GameState::LoadFailed), reset_progress);
+    }
+}
+
+// Progress is hidden so progress bars only count files, it keeps the state from continuing
+// before the loader has had a chance to report anything
+fn track_collections(
+    asset_server: Res<AssetServer>,
+    config: Res<LoadingConfig>,
+    mut progress: ResMut<LoadingProgress>,
+    audio: Option<Res<AudioAssets>>,
+    levels: Option<Res<LevelAssets>>,
+) -> HiddenProgress {
+    let reported: Vec<_> = progress.failures().cloned().collect();
+    for ((_, assets), collection) in config
+        .paths
+        .collections()
+        .iter()
+        .zip(progress.collections.iter_mut())
+    {
+        collection.done = 0;
+        collection.failures.clear();
+        for (_, path) in assets {
+            match asset_server
+                .get_path_id(path.to_string())
+                .and_then(|id| asset_server.get_load_state(id))
+            {
+                Some(LoadState::Loaded) => collection.done += 1,
+                Some(LoadState::Failed(error)) => {
+                    collection
+                        .failures
+                        .push((path.to_string(), error.to_string()));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    for (path, reason) in progress.failures() {
+        if !reported.iter().any(|(reported, _)| reported == path) {
+            error!("Failed to load {path}: {reason}");
+        }
+    }
+
+    let created = [audio.is_some(), levels.is_some()];
+    HiddenProgress(Progress {
+        done: created.iter().filter(|created| **created).count() as u32,
+        total: created.len() as u32,
+    })
+}
+
+// Whatever fails again after retrying is reported again
+fn reset_progress(config: Res<LoadingConfig>, mut progress: ResMut<LoadingProgress>) {
+    // level assets are only loaded again once a level is
+    progress
+        .collections
+        .truncate(config.paths.collections().len());
+    for collection in progress.collections.iter_mut() {
+        collection.done = 0;
+        collection.failures.clear();
+    }
+}
+
+/// Insert placeholders for every collection that failed to load, so the game can still be
+/// entered during development
+pub fn insert_fallback_collections(world: &mut World) {
+    if !world.contains_resource::<AudioAssets>() {
+        world.insert_resource(AudioAssets {
+            intro: Handle::default(),
+        });
+    }
+    if !world.contains_resource::<LevelAssets>() {
+        let registry = world
+            .resource_mut::<Assets<LevelRegistry>>()
+            .add(LevelRegistry::fallback());
+        world.insert_resource(LevelAssets { registry });
+    }
+}
+
+/// Plain dark cubemap used in place of a missing or malformed skybox
+pub fn fallback_skybox() -> Image {
+    let mut image = Image::new_fill(
+        Extent3d {
+            width: 1,
+            height: 1,
+            depth_or_array_layers: 6,
+        },
+        TextureDimension::D2,
+        &[5, 5, 8, 255],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    image.texture_view_descriptor = Some(TextureViewDescriptor {
+        dimension: Some(TextureViewDimension::Cube),
+        ..default()
+    });
+    image
+}
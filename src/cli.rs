@@ -0,0 +1,103 @@
+use bevy::prelude::*;
+use bevy::window::{WindowMode, WindowResolution};
+use clap::Parser;
+
+use super::{HorrorPlugins, GAME_NAME};
+
+/// Launcher options, mostly to jump straight to what is being worked on
+#[derive(Parser, Debug, Default)]
+#[command(version, about = GAME_NAME)]
+pub struct Args {
+    /// Go straight into the game once assets have loaded, without the splash screen or menu
+    #[arg(long)]
+    pub skip_splash: bool,
+    /// Level to start in, instead of the first in the registry
+    #[arg(long, value_name = "NAME")]
+    pub level: Option<String>,
+    /// Seed for all gameplay randomness, overrides `HORROR_SEED`
+    #[arg(long)]
+    pub seed: Option<u64>,
+    /// Run in a window, undoing an earlier `--fullscreen` so the last of the two wins,
+    /// e.g. when appended to a shell alias
+    #[arg(long, overrides_with = "fullscreen")]
+    pub windowed: bool,
+    /// Run borderless fullscreen, undoing an earlier `--windowed`
+    #[arg(long, overrides_with = "windowed")]
+    pub fullscreen: bool,
+    /// Window size, e.g. `1280x720`
+    #[arg(long, value_name = "WIDTHxHEIGHT", value_parser = parse_resolution)]
+    pub resolution: Option<(f32, f32)>,
+    /// Leave out the VHS post-processing
+    #[arg(long)]
+    pub no_shaders: bool,
+    #[arg(long)]
+    pub mute: bool,
+    /// Where the player spawns in the start level, e.g. `0,0,-15`
+    #[arg(long, value_name = "X,Y,Z", value_parser = parse_vec3, allow_hyphen_values = true)]
+    pub spawn: Option<Vec3>,
+    /// Language to start in, e.g. `fr`
+    #[arg(long, value_name = "ID")]
+    pub language: Option<String>,
+}
+
+impl Args {
+    pub fn plugins(&self) -> HorrorPlugins {
+        HorrorPlugins {
+            menu: !self.skip_splash,
+            splash: !self.skip_splash,
+            shaders: !self.no_shaders,
+            seed: self.seed,
+            level: self.level.clone(),
+            spawn: self.spawn,
+            locale: self.language.clone(),
+            ..default()
+        }
+    }
+
+    pub fn window(&self) -> Window {
+        let mut window = Window {
+            title: GAME_NAME.into(),
+            ..default()
+        };
+        if self.fullscreen && !self.windowed {
+            window.mode = WindowMode::BorderlessFullscreen;
+        }
+        if let Some((width, height)) = self.resolution {
+            window.resolution = WindowResolution::new(width, height);
+        }
+        window
+    }
+
+    pub fn volume(&self) -> GlobalVolume {
+        match self.mute {
+            true => GlobalVolume::new(0.0),
+            false => GlobalVolume::default(),
+        }
+    }
+}
+
+fn parse_resolution(value: &str) -> Result<(f32, f32), String> {
+    let (width, height) = value
+        .split_once('x')
+        .ok_or_else(|| format!("expected WIDTHxHEIGHT, got `{value}`"))?;
+    let parse = |side: &str| {
+        side.trim()
+            .parse::<f32>()
+            .ok()
+            .filter(|side| *side > 0.0)
+            .ok_or_else(|| format!("`{side}` is not a valid size"))
+    };
+    Ok((parse(width)?, parse(height)?))
+}
+
+fn parse_vec3(value: &str) -> Result<Vec3, String> {
+    let components = value
+        .split(',')
+        .map(|component| component.trim().parse::<f32>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|error| error.to_string())?;
+    match components[..] {
+        [x, y, z] => Ok(Vec3::new(x, y, z)),
+        _ => Err(format!("expected X,Y,Z, got `{value}`")),
+    }
+}
@@ -0,0 +1,474 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use bevy_rapier3d::prelude::*;
+use leafwing_input_manager::prelude::*;
+
+use super::GameState;
+use crate::game::g3d::Action;
+use crate::game::level::LevelScoped;
+#[cfg(feature = "shaders")]
+use crate::game::vhs::VhsPreset;
+use crate::game::{GameSession, Inventory, Player};
+
+const TOGGLE_KEY: KeyCode = KeyCode::Backquote;
+const NOCLIP_SPEED: f32 = 8.0;
+
+/// Drop-down developer console, opened with the key left of `1`
+pub struct ConsolePlugin;
+
+impl Plugin for ConsolePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Console>()
+            .insert_resource(ConsoleCommands::builtin())
+            .add_systems(Update, (toggle_console, ui, run_commands).chain())
+            .add_systems(Update, noclip_movement.run_if(in_state(GameState::Game)));
+    }
+}
+
+/// Runs a command with its arguments, returning what to print
+pub type CommandHandler = fn(&mut World, &[&str]) -> Result<String, String>;
+
+pub struct ConsoleCommand {
+    pub name: &'static str,
+    pub usage: &'static str,
+    /// Values offered when completing each argument
+    pub arguments: &'static [&'static [&'static str]],
+    pub run: CommandHandler,
+}
+
+/// Every command the console knows about, more can be registered by other plugins
+#[derive(Resource, Default)]
+pub struct ConsoleCommands(Vec<ConsoleCommand>);
+
+impl ConsoleCommands {
+    pub fn register(&mut self, command: ConsoleCommand) -> &mut Self {
+        self.0.retain(|existing| existing.name != command.name);
+        self.0.push(command);
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ConsoleCommand> {
+        self.0.iter().find(|command| command.name == name)
+    }
+
+    /// Complete the last word of `input`, as far as every candidate agrees,
+    /// along with the candidates themselves
+    pub fn complete(&self, input: &str) -> (String, Vec<&'static str>) {
+        let words: Vec<&str> = input.split_whitespace().collect();
+        // a trailing space starts the next word
+        let next_word = input.is_empty() || input.ends_with(' ');
+        let (done, partial) = match (next_word, words.split_last()) {
+            (false, Some((partial, done))) => (done, *partial),
+            _ => (&words[..], ""),
+        };
+
+        let options: Vec<&'static str> = match done.split_first() {
+            None => std::iter::once("help")
+                .chain(self.0.iter().map(|command| command.name))
+                .collect(),
+            Some((name, arguments)) => self
+                .get(name)
+                .and_then(|command| command.arguments.get(arguments.len()))
+                .map(|options| options.to_vec())
+                .unwrap_or_default(),
+        };
+        let candidates: Vec<&'static str> = options
+            .into_iter()
+            .filter(|option| option.starts_with(partial))
+            .collect();
+
+        let mut completed = done.join(" ");
+        if !done.is_empty() {
+            completed.push(' ');
+        }
+        match candidates[..] {
+            [] => return (input.to_string(), candidates),
+            [only] => {
+                completed.push_str(only);
+                completed.push(' ');
+            }
+            [first, ..] => {
+                let common = candidates.iter().fold(first.len(), |common, candidate| {
+                    first
+                        .chars()
+                        .zip(candidate.chars())
+                        .take(common)
+                        .take_while(|(a, b)| a == b)
+                        .count()
+                });
+                completed.push_str(&first[..common.max(partial.len())]);
+            }
+        }
+        (completed, candidates)
+    }
+
+    /// Run a line typed into the console
+    pub fn run(&self, world: &mut World, line: &str) -> Result<String, String> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let Some((name, arguments)) = words.split_first() else {
+            return Ok(String::new());
+        };
+        if *name == "help" {
+            return Ok(self
+                .0
+                .iter()
+                .map(|command| command.usage)
+                .collect::<Vec<_>>()
+                .join("\n"));
+        }
+        match self.get(name) {
+            Some(command) => (command.run)(world, arguments),
+            None => Err(format!("unknown command `{name}`, try `help`")),
+        }
+    }
+
+    pub fn builtin() -> Self {
+        let mut commands = Self::default();
+        commands
+            .register(ConsoleCommand {
+                name: "noclip",
+                usage: "noclip - fly through walls",
+                arguments: &[],
+                run: noclip,
+            })
+            .register(ConsoleCommand {
+                name: "tp",
+                usage: "tp <x> <y> <z> - move the player",
+                arguments: &[],
+                run: teleport,
+            })
+            .register(ConsoleCommand {
+                name: "give",
+                usage: "give <item> - add an item to the inventory",
+                arguments: &[&["key", "tape"]],
+                run: give,
+            })
+            .register(ConsoleCommand {
+                name: "state",
+                usage: "state game|menu - switch the game state",
+                arguments: &[&["game", "menu"]],
+                run: state,
+            })
+            .register(ConsoleCommand {
+                name: "spawn",
+                usage: "spawn enemy - place a stand-in enemy in front of the player",
+                arguments: &[&["enemy"]],
+                run: spawn,
+            })
+            .register(ConsoleCommand {
+                name: "timescale",
+                usage: "timescale <speed> - speed up or slow down time, 1 is normal",
+                arguments: &[&["0.25", "0.5", "1", "2", "4"]],
+                run: timescale,
+            })
+            .register(ConsoleCommand {
+                name: "flashlight",
+                usage: "flashlight battery <percent> - charge the flashlight",
+                arguments: &[&["battery"], &["0", "10", "100"]],
+                run: flashlight,
+            });
+        #[cfg(feature = "shaders")]
+        commands.register(ConsoleCommand {
+            name: "vhs",
+            usage: "vhs clean|worn|damaged - switch the tape look",
+            arguments: &[&["clean", "worn", "damaged"]],
+            run: vhs,
+        });
+        commands
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct Console {
+    pub open: bool,
+    input: String,
+    log: Vec<String>,
+    history: Vec<String>,
+    // position in `history` while browsing it with the arrow keys
+    browsing: Option<usize>,
+    suggestions: Vec<&'static str>,
+    pending: Vec<String>,
+}
+
+// The player's controller and body, put aside while flying
+#[derive(Component)]
+struct Noclip {
+    controller: KinematicCharacterController,
+    body: RigidBody,
+}
+
+fn toggle_console(keys: Res<ButtonInput<KeyCode>>, mut console: ResMut<Console>) {
+    if keys.just_pressed(TOGGLE_KEY) {
+        console.open = !console.open;
+    }
+}
+
+fn ui(mut contexts: EguiContexts, mut console: ResMut<Console>, commands: Res<ConsoleCommands>) {
+    if !console.open {
+        return;
+    }
+    let ctx = contexts.ctx_mut();
+    let console = &mut *console;
+
+    egui::TopBottomPanel::top("console").show(ctx, |ui| {
+        egui::ScrollArea::vertical()
+            .max_height(200.0)
+            .stick_to_bottom(true)
+            .show(ui, |ui| {
+                ui.set_min_width(ui.available_width());
+                for line in &console.log {
+                    ui.monospace(line);
+                }
+            });
+
+        let id = egui::Id::new("console input");
+        let response = ui.add(
+            egui::TextEdit::singleline(&mut console.input)
+                .id(id)
+                .font(egui::TextStyle::Monospace)
+                .desired_width(f32::INFINITY)
+                .lock_focus(true),
+        );
+        // the toggle key itself shouldn't end up in the input
+        console.input.retain(|c| c != '`' && c != '\t');
+
+        let (submit, tab, up, down) = ui.input(|input| {
+            (
+                input.key_pressed(egui::Key::Enter),
+                input.key_pressed(egui::Key::Tab),
+                input.key_pressed(egui::Key::ArrowUp),
+                input.key_pressed(egui::Key::ArrowDown),
+            )
+        });
+        let mut moved = false;
+
+        if submit && response.lost_focus() {
+            let line = std::mem::take(&mut console.input).trim().to_string();
+            if !line.is_empty() {
+                console.log.push(format!("> {line}"));
+                if console.history.last() != Some(&line) {
+                    console.history.push(line.clone());
+                }
+                console.pending.push(line);
+            }
+            console.browsing = None;
+            console.suggestions.clear();
+        } else if tab {
+            let (completed, candidates) = commands.complete(&console.input);
+            console.input = completed;
+            console.suggestions = candidates;
+            moved = true;
+        } else if up || down {
+            let last = console.history.len().checked_sub(1);
+            console.browsing = match (console.browsing, up) {
+                (None, true) => last,
+                (Some(index), true) => Some(index.saturating_sub(1)),
+                (Some(index), false) if Some(index) != last => Some(index + 1),
+                _ => None,
+            };
+            console.input = console
+                .browsing
+                .map(|index| console.history[index].clone())
+                .unwrap_or_default();
+            moved = true;
+        }
+
+        if moved {
+            if let Some(mut state) = egui::TextEdit::load_state(ui.ctx(), id) {
+                let end = egui::text::CCursor::new(console.input.chars().count());
+                state
+                    .cursor
+                    .set_char_range(Some(egui::text::CCursorRange::one(end)));
+                state.store(ui.ctx(), id);
+            }
+        }
+        response.request_focus();
+
+        if !console.suggestions.is_empty() {
+            ui.weak(console.suggestions.join("  "));
+        }
+    });
+}
+
+fn run_commands(world: &mut World) {
+    let pending = std::mem::take(&mut world.resource_mut::<Console>().pending);
+    for line in pending {
+        let result = world
+            .resource_scope(|world, commands: Mut<ConsoleCommands>| commands.run(world, &line));
+        let output = match result {
+            Ok(output) => output,
+            Err(error) => format!("error: {error}"),
+        };
+        let mut console = world.resource_mut::<Console>();
+        console.log.extend(
+            output
+                .lines()
+                .map(str::to_string)
+                .filter(|line| !line.is_empty()),
+        );
+    }
+}
+
+fn players(world: &mut World) -> Result<Vec<Entity>, String> {
+    let players: Vec<Entity> = world
+        .query_filtered::<Entity, With<Player>>()
+        .iter(world)
+        .collect();
+    if players.is_empty() {
+        return Err("no player, start the game first".into());
+    }
+    Ok(players)
+}
+
+fn parse<T: std::str::FromStr>(argument: Option<&&str>, usage: &str) -> Result<T, String> {
+    argument
+        .and_then(|argument| argument.parse().ok())
+        .ok_or_else(|| format!("usage: {usage}"))
+}
+
+fn noclip(world: &mut World, _: &[&str]) -> Result<String, String> {
+    let mut enabled = false;
+    for player in players(world)? {
+        let mut player = world.entity_mut(player);
+        match player.take::<Noclip>() {
+            Some(noclip) => {
+                player
+                    .insert((noclip.controller, noclip.body))
+                    .remove::<ColliderDisabled>();
+            }
+            None => {
+                let controller = player.take::<KinematicCharacterController>();
+                let body = player.take::<RigidBody>();
+                player.insert((
+                    Noclip {
+                        controller: controller.unwrap_or_default(),
+                        body: body.unwrap_or_default(),
+                    },
+                    RigidBody::KinematicPositionBased,
+                    ColliderDisabled,
+                ));
+                enabled = true;
+            }
+        }
+    }
+    Ok(format!("noclip {}", if enabled { "on" } else { "off" }))
+}
+
+fn noclip_movement(
+    time: Res<Time>,
+    mut query: Query<(&mut Transform, &ActionState<Action>), With<Noclip>>,
+) {
+    for (mut transform, action_state) in query.iter_mut() {
+        let axis_pair = action_state.clamped_axis_pair(&Action::Move);
+        let translation = transform.forward() * axis_pair.y - transform.left() * axis_pair.x;
+        transform.translation += translation * NOCLIP_SPEED * time.delta_seconds();
+    }
+}
+
+fn teleport(world: &mut World, arguments: &[&str]) -> Result<String, String> {
+    let usage = "tp <x> <y> <z>";
+    let [x, y, z] = [0, 1, 2].map(|index| parse::<f32>(arguments.get(index), usage));
+    let position = Vec3::new(x?, y?, z?);
+    for player in players(world)? {
+        if let Some(mut transform) = world.get_mut::<Transform>(player) {
+            transform.translation = position;
+        }
+    }
+    Ok(format!("teleported to {position}"))
+}
+
+fn give(world: &mut World, arguments: &[&str]) -> Result<String, String> {
+    let item = arguments
+        .first()
+        .ok_or_else(|| "usage: give <item>".to_string())?;
+    for player in players(world)? {
+        if let Some(mut inventory) = world.get_mut::<Inventory>(player) {
+            inventory.0.push(item.to_string());
+        }
+    }
+    Ok(format!("gave {item}"))
+}
+
+fn state(world: &mut World, arguments: &[&str]) -> Result<String, String> {
+    let state = match arguments.first() {
+        Some(&"game") => GameState::Game,
+        Some(&"menu") => GameState::Menu,
+        _ => return Err("usage: state game|menu".into()),
+    };
+    world
+        .resource_mut::<NextState<GameState>>()
+        .set(state.clone());
+    Ok(format!("switching to {state:?}"))
+}
+
+fn spawn(world: &mut World, arguments: &[&str]) -> Result<String, String> {
+    if arguments.first() != Some(&"enemy") {
+        return Err("usage: spawn enemy".into());
+    }
+    let player = players(world)?[0];
+    let transform = world.get::<Transform>(player).copied().unwrap_or_default();
+    let position = transform.translation + transform.forward() * 3.0;
+
+    let mesh = world
+        .resource_mut::<Assets<Mesh>>()
+        .add(Capsule3d::new(0.3, 1.2));
+    let material = world
+        .resource_mut::<Assets<StandardMaterial>>()
+        .add(StandardMaterial {
+            base_color: Color::srgb(0.05, 0.05, 0.05),
+            ..default()
+        });
+    world.spawn((
+        PbrBundle {
+            mesh,
+            material,
+            transform: Transform::from_translation(position),
+            ..default()
+        },
+        RigidBody::Dynamic,
+        LockedAxes::ROTATION_LOCKED,
+        Collider::capsule_y(0.6, 0.3),
+        Name::new("enemy"),
+        LevelScoped,
+        GameSession,
+    ));
+    Ok(format!("spawned enemy at {position}"))
+}
+
+fn timescale(world: &mut World, arguments: &[&str]) -> Result<String, String> {
+    let speed: f32 = parse(arguments.first(), "timescale <speed>")?;
+    if speed < 0.0 {
+        return Err("speed can't be negative".into());
+    }
+    world
+        .resource_mut::<Time<Virtual>>()
+        .set_relative_speed(speed);
+    Ok(format!("timescale {speed}"))
+}
+
+fn flashlight(world: &mut World, arguments: &[&str]) -> Result<String, String> {
+    let usage = "flashlight battery <percent>";
+    if arguments.first() != Some(&"battery") {
+        return Err(format!("usage: {usage}"));
+    }
+    let battery = parse::<f32>(arguments.get(1), usage)?.clamp(0.0, 100.0);
+    for player in players(world)? {
+        if let Some(mut player) = world.get_mut::<Player>(player) {
+            player.battery = battery;
+        }
+    }
+    Ok(format!("flashlight battery {battery}%"))
+}
+
+#[cfg(feature = "shaders")]
+fn vhs(world: &mut World, arguments: &[&str]) -> Result<String, String> {
+    let preset = VhsPreset::ALL
+        .into_iter()
+        .find(|preset| arguments.first() == Some(&preset.name()))
+        .ok_or_else(|| "usage: vhs clean|worn|damaged".to_string())?;
+    let Some(mut current) = world.get_resource_mut::<VhsPreset>() else {
+        return Err("the VHS shader is disabled".into());
+    };
+    *current = preset;
+    Ok(format!("vhs {}", preset.name()))
+}
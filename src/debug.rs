@@ -1,12 +1,97 @@
-use bevy::{input::common_conditions::input_toggle_active, prelude::*};
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
 use bevy_inspector_egui::quick::WorldInspectorPlugin;
+use leafwing_input_manager::prelude::*;
+
+use crate::game::house::CurrentHouse;
+use crate::rng::GameRng;
 
 pub struct DebugPlugin;
 
 impl Plugin for DebugPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(
-            WorldInspectorPlugin::default().run_if(input_toggle_active(true, KeyCode::Escape)),
-        );
+        // kept on the function keys so none of them clash with gameplay bindings
+        let input_map = InputMap::new([
+            (DebugAction::Inspector, KeyCode::F1),
+            (DebugAction::Colliders, KeyCode::F2),
+            (DebugAction::Overlay, KeyCode::F3),
+            (DebugAction::FreeCamera, KeyCode::F4),
+            (DebugAction::Performance, KeyCode::F5),
+        ]);
+
+        app.add_plugins(InputManagerPlugin::<DebugAction>::default())
+            .init_resource::<ActionState<DebugAction>>()
+            .insert_resource(input_map)
+            .init_resource::<DebugTools>()
+            .add_plugins(
+                WorldInspectorPlugin::default().run_if(|tools: Res<DebugTools>| tools.inspector),
+            )
+            .add_systems(
+                Update,
+                (
+                    toggle_tools,
+                    overlay.run_if(|tools: Res<DebugTools>| tools.overlay),
+                )
+                    .chain(),
+            );
+    }
+}
+
+#[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Reflect)]
+pub enum DebugAction {
+    Inspector,
+    Colliders,
+    Overlay,
+    FreeCamera,
+    Performance,
+}
+
+/// Which debug tools are showing, each toggled by its own `DebugAction`
+#[derive(Resource, Debug)]
+pub struct DebugTools {
+    pub inspector: bool,
+    /// Rapier's debug render of every collider
+    pub colliders: bool,
+    pub overlay: bool,
+    /// Fly around with a detached camera while the player stays put
+    pub free_camera: bool,
+    /// Frame time graph and counters, see `PerfPlugin`
+    pub performance: bool,
+}
+
+impl Default for DebugTools {
+    fn default() -> Self {
+        Self {
+            inspector: true,
+            colliders: true,
+            overlay: true,
+            free_camera: false,
+            performance: false,
+        }
     }
 }
+
+fn toggle_tools(actions: Res<ActionState<DebugAction>>, mut tools: ResMut<DebugTools>) {
+    for action in actions.get_just_pressed() {
+        let tool = match action {
+            DebugAction::Inspector => &mut tools.inspector,
+            DebugAction::Colliders => &mut tools.colliders,
+            DebugAction::Overlay => &mut tools.overlay,
+            DebugAction::FreeCamera => &mut tools.free_camera,
+            DebugAction::Performance => &mut tools.performance,
+        };
+        *tool = !*tool;
+    }
+}
+
+fn overlay(mut contexts: EguiContexts, rng: Res<GameRng>, house: Option<Res<CurrentHouse>>) {
+    egui::Area::new(egui::Id::new("debug overlay"))
+        .anchor(egui::Align2::RIGHT_TOP, egui::vec2(-10.0, 10.0))
+        .show(contexts.ctx_mut(), |ui| {
+            ui.label(format!("seed {}", rng.seed()));
+            if let Some(house) = house {
+                ui.label(format!("house seed {}", house.0));
+            }
+            ui.weak("F1 inspector  F2 colliders  F3 overlay  F4 free camera  F5 performance");
+        });
+}
@@ -0,0 +1,63 @@
+use crate::accessibility::Accessibility;
+use crate::assets::{insert_fallback_collections, LoadingConfig, LoadingProgress};
+use crate::localization::Localization;
+use crate::osd::{self, Backdrop, OsdMenu};
+
+use super::GameState;
+
+use bevy::prelude::*;
+use bevy_egui::egui;
+
+pub struct ErrorPlugin;
+
+impl Plugin for ErrorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, ui.run_if(in_state(GameState::LoadFailed)));
+    }
+}
+
+fn ui(
+    mut menu: OsdMenu,
+    mut commands: Commands,
+    progress: Res<LoadingProgress>,
+    accessibility: Res<Accessibility>,
+    localization: Res<Localization>,
+    config: Res<LoadingConfig>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut writer: EventWriter<AppExit>,
+) {
+    menu.show("load failed", Backdrop::Dim, |ui, focus| {
+        let text = |key: &str| localization.text(key);
+        ui.label(osd::text(text("error-no-signal"), 64.0));
+        ui.label(osd::text(text("error-load-failed"), 24.0));
+
+        ui.add_space(10.0);
+
+        for (path, reason) in progress.failures() {
+            ui.label(
+                osd::text(format!("{path}: {reason}"), 18.0).color(accessibility.danger_egui()),
+            );
+        }
+
+        ui.add_space(10.0);
+
+        let retry = focus.button(ui, osd::text(text("error-retry"), 32.0));
+        // placeholders stand in for whatever is missing, only useful while developing
+        let fallback =
+            cfg!(debug_assertions) && focus.button(ui, osd::text(text("error-fallbacks"), 24.0));
+        let quit = focus.button(ui, osd::text(text("error-quit"), 24.0));
+
+        if retry {
+            next_state.set(GameState::Splash);
+        }
+
+        if fallback {
+            commands.add(insert_fallback_collections);
+            next_state.set(config.next_state.clone());
+        }
+
+        if quit {
+            writer.send(AppExit::Success);
+        }
+    });
+}
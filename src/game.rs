@@ -1,91 +1,158 @@
+pub mod camcorder;
+pub mod capture;
 #[cfg(feature = "debug")]
-mod debug3d;
-mod g2d;
-mod g3d;
-mod pause;
+pub mod debug3d;
+pub mod g2d;
+pub mod g3d;
+pub mod house;
+pub mod level;
+pub mod objectives;
+pub mod pause;
 #[cfg(feature = "shaders")]
-mod vhs;
+pub mod vhs;
 
 use super::{despawn_screen, GameState};
-use bevy::gltf::Gltf;
+#[cfg(debug_assertions)]
+use crate::warn_cardinality;
 use bevy::prelude::*;
-use bevy_asset_loader::prelude::*;
+use bevy_rapier3d::prelude::*;
 use leafwing_input_manager::prelude::*;
 
 #[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, States)]
-enum GameplayState {
+pub enum GameplayState {
     #[default]
     Playing,
     Paused,
 }
 
 #[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Reflect)]
-enum Action {
+pub enum Action {
     Pause,
 }
 
-#[derive(AssetCollection, Resource)]
-pub struct AudioAssets {
-    #[asset(path = "audio/haunting_piano.ogg")]
-    intro: Handle<AudioSource>,
-}
+/// Tag component for everything spawned while in `GameState::Game`,
+/// all of it is despawned when the session ends
+#[derive(Component)]
+pub struct GameSession;
 
-#[derive(AssetCollection, Resource)]
-pub struct GltfAssets {
-    #[asset(path = "models/world.glb")]
-    house: Handle<Gltf>,
-}
+#[derive(Component)]
+pub(super) struct PlayerCamera;
 
-#[derive(AssetCollection, Resource)]
-pub struct TextureAssets {
-    #[asset(path = "textures/skybox.png")]
-    skybox: Handle<Image>,
-}
+/// Charge the flashlight loses per second while it is on, in percent
+pub const BATTERY_DRAIN: f32 = 0.05;
+/// How far the player's camera can zoom in, as a multiple of its normal field of view
+pub const MAX_ZOOM: f32 = 10.0;
 
-#[derive(Component, Default)]
-pub(super) struct Player {
+#[derive(Component)]
+pub struct Player {
     flashlight_flicker: Timer,
+    /// Flashlight charge in percent, the light stays off once it runs out
+    pub battery: f32,
 }
 
+impl Default for Player {
+    fn default() -> Self {
+        Self {
+            flashlight_flicker: Timer::from_seconds(0.1, TimerMode::Once),
+            battery: 100.0,
+        }
+    }
+}
+
+/// Items the player is carrying
+#[derive(Component, Default, Debug)]
+pub struct Inventory(pub Vec<String>);
+
 pub struct GamePlugin;
 
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((
-            InputManagerPlugin::<Action>::default(),
-            g2d::G2dPlugin,
-            g3d::G3dPlugin,
-            #[cfg(feature = "shaders")]
-            vhs::VHSPlugin,
-            pause::PausePlugin,
-            #[cfg(feature = "debug")]
-            debug3d::Debug3DPlugin,
-        ))
-        .init_state::<GameplayState>()
-        .add_systems(OnEnter(GameState::Game), setup)
-        .add_systems(Update, toggle_pause.run_if(in_state(GameState::Game)));
+        app.add_plugins(InputManagerPlugin::<Action>::default())
+            .init_state::<GameplayState>()
+            .add_systems(OnEnter(GameState::Game), setup)
+            .add_systems(Update, toggle_pause.run_if(in_state(GameState::Game)))
+            .add_systems(OnEnter(GameplayState::Paused), freeze)
+            .add_systems(OnExit(GameplayState::Paused), unfreeze)
+            .add_systems(
+                OnExit(GameState::Game),
+                (despawn_screen::<GameSession>, teardown),
+            );
+
+        // Systems above tolerate any number of these, but anything other than the expected
+        // count usually means something was spawned twice or never spawned at all
+        #[cfg(debug_assertions)]
+        app.add_systems(
+            Update,
+            (
+                warn_cardinality::<ActionState<Action>>(1..=1),
+                warn_cardinality::<Player>(1..=usize::MAX),
+            )
+                .run_if(in_state(GameState::Game)),
+        );
     }
 }
 
 fn setup(mut commands: Commands) {
-    let input_map = InputMap::new([(Action::Pause, KeyCode::Escape)]);
+    let input_map = InputMap::new([(Action::Pause, KeyCode::Escape)])
+        .with(Action::Pause, GamepadButtonType::Start);
+
+    commands.spawn((
+        InputManagerBundle::<Action> {
+            action_state: ActionState::default(),
+            input_map,
+        },
+        GameSession,
+    ));
+}
 
-    commands.spawn(InputManagerBundle::<Action> {
-        action_state: ActionState::default(),
-        input_map,
-    });
+// Restore the resources a session overrides so the next playthrough starts fresh
+fn teardown(mut commands: Commands, mut next_state: ResMut<NextState<GameplayState>>) {
+    commands.insert_resource(AmbientLight::default());
+    next_state.set(GameplayState::Playing);
 }
 
+// Stop everything a session simulates, so nothing carries on behind the pause menu
+fn freeze(
+    mut time: ResMut<Time<Virtual>>,
+    physics: Option<ResMut<RapierConfiguration>>,
+    sinks: Query<&AudioSink, With<GameSession>>,
+    spatial_sinks: Query<&SpatialAudioSink, With<GameSession>>,
+) {
+    time.pause();
+    if let Some(mut physics) = physics {
+        physics.physics_pipeline_active = false;
+    }
+    sinks.iter().for_each(AudioSinkPlayback::pause);
+    spatial_sinks.iter().for_each(AudioSinkPlayback::pause);
+}
+
+fn unfreeze(
+    mut time: ResMut<Time<Virtual>>,
+    physics: Option<ResMut<RapierConfiguration>>,
+    sinks: Query<&AudioSink, With<GameSession>>,
+    spatial_sinks: Query<&SpatialAudioSink, With<GameSession>>,
+) {
+    time.unpause();
+    if let Some(mut physics) = physics {
+        physics.physics_pipeline_active = true;
+    }
+    sinks.iter().for_each(AudioSinkPlayback::play);
+    spatial_sinks.iter().for_each(AudioSinkPlayback::play);
+}
+
+// Only pauses, the pause menu decides when to resume as Escape also backs out of its screens
 fn toggle_pause(
     state: Res<State<GameplayState>>,
     mut next_state: ResMut<NextState<GameplayState>>,
     query: Query<&ActionState<Action>>,
 ) {
-    let action_state = query.single();
-    if action_state.just_pressed(&Action::Pause) {
-        match state.get() {
-            GameplayState::Playing => next_state.set(GameplayState::Paused),
-            GameplayState::Paused => next_state.set(GameplayState::Playing),
-        }
+    if *state.get() == GameplayState::Playing && pause_pressed(&query) {
+        next_state.set(GameplayState::Paused);
     }
 }
+
+pub(crate) fn pause_pressed(query: &Query<&ActionState<Action>>) -> bool {
+    query
+        .iter()
+        .any(|action_state| action_state.just_pressed(&Action::Pause))
+}
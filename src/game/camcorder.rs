@@ -0,0 +1,179 @@
+use crate::accessibility::{Accessibility, Latch};
+use crate::GameState;
+
+use super::{GameplayState, Player, PlayerCamera, MAX_ZOOM};
+use bevy::prelude::*;
+use bevy::render::view::ColorGrading;
+use leafwing_input_manager::prelude::*;
+use std::f32::consts::FRAC_PI_4;
+
+/// Each zoom step multiplies the zoom by this, four steps double it
+const ZOOM_STEP: f32 = 1.189_207;
+// how quickly the field of view catches up with the zoom
+const ZOOM_EASE: f32 = 12.0;
+/// How much faster the battery drains with the infrared light on instead of the flashlight
+pub const NIGHT_VISION_DRAIN: f32 = 3.0;
+const IR_INTENSITY: f32 = 400.0;
+
+/// The camcorder the player can raise to look through, zooming in or switching to night vision
+pub struct CamcorderPlugin;
+
+impl Plugin for CamcorderPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(InputManagerPlugin::<CamcorderAction>::default())
+            .add_systems(
+                Update,
+                (operate, apply_view)
+                    .chain()
+                    .run_if(in_state(GameState::Game).and_then(in_state(GameplayState::Playing))),
+            );
+    }
+}
+
+#[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Reflect)]
+pub enum CamcorderAction {
+    /// Held, or toggled with [`Accessibility::hold_to_toggle`]
+    Raise,
+    ZoomIn,
+    ZoomOut,
+    NightVision,
+    /// Take a snapshot for the gallery, see [`super::capture`]
+    Capture,
+}
+
+impl CamcorderAction {
+    pub fn input_map() -> InputMap<Self> {
+        InputMap::new([
+            (Self::Raise, MouseButton::Right),
+            (Self::NightVision, MouseButton::Middle),
+            (Self::Capture, MouseButton::Left),
+        ])
+        .with(Self::NightVision, KeyCode::KeyN)
+        .with(Self::ZoomIn, MouseScrollDirection::UP)
+        .with(Self::ZoomOut, MouseScrollDirection::DOWN)
+        .with_multiple([
+            (Self::Raise, GamepadButtonType::LeftTrigger2),
+            (Self::ZoomIn, GamepadButtonType::RightTrigger),
+            (Self::ZoomOut, GamepadButtonType::LeftTrigger),
+            (Self::NightVision, GamepadButtonType::North),
+            (Self::Capture, GamepadButtonType::RightTrigger2),
+        ])
+    }
+}
+
+/// State of the player's camcorder, kept on the [`Player`]
+#[derive(Component, Debug)]
+pub struct Camcorder {
+    pub raised: bool,
+    /// Digital zoom, from 1 to [`MAX_ZOOM`], only applies while raised
+    pub zoom: f32,
+    /// Infrared mode, only applies while raised and there is battery left
+    pub night_vision: bool,
+    raise: Latch,
+}
+
+impl Default for Camcorder {
+    fn default() -> Self {
+        Self {
+            raised: false,
+            zoom: 1.0,
+            night_vision: false,
+            raise: Latch::default(),
+        }
+    }
+}
+
+impl Camcorder {
+    pub fn zoom_in(&mut self) {
+        self.zoom = (self.zoom * ZOOM_STEP).min(MAX_ZOOM);
+    }
+
+    pub fn zoom_out(&mut self) {
+        self.zoom = (self.zoom / ZOOM_STEP).max(1.0);
+    }
+
+    /// Field of view the player's camera should have
+    pub fn fov(&self) -> f32 {
+        match self.raised {
+            true => FRAC_PI_4 / self.zoom,
+            false => FRAC_PI_4,
+        }
+    }
+
+    /// Whether the player is looking through night vision, which needs battery to light the
+    /// scene in infrared
+    pub fn night_vision_on(&self, player: &Player) -> bool {
+        self.raised && self.night_vision && player.battery > 0.0
+    }
+}
+
+/// Infrared light lighting the way for night vision, in place of the flashlight
+#[derive(Component)]
+pub(super) struct IrLight;
+
+fn operate(
+    accessibility: Res<Accessibility>,
+    mut query: Query<(&mut Camcorder, &ActionState<CamcorderAction>)>,
+) {
+    for (mut camcorder, action_state) in query.iter_mut() {
+        let camcorder = &mut *camcorder;
+        camcorder.raised =
+            camcorder
+                .raise
+                .active(&accessibility, action_state, &CamcorderAction::Raise);
+        if !camcorder.raised {
+            continue;
+        }
+        if action_state.just_pressed(&CamcorderAction::ZoomIn) {
+            camcorder.zoom_in();
+        }
+        if action_state.just_pressed(&CamcorderAction::ZoomOut) {
+            camcorder.zoom_out();
+        }
+        if action_state.just_pressed(&CamcorderAction::NightVision) {
+            camcorder.night_vision = !camcorder.night_vision;
+        }
+    }
+}
+
+fn apply_view(
+    time: Res<Time>,
+    accessibility: Res<Accessibility>,
+    players: Query<(&Camcorder, &Player)>,
+    mut cameras: Query<(&mut Projection, &mut ColorGrading), With<PlayerCamera>>,
+    mut ir_lights: Query<&mut SpotLight, With<IrLight>>,
+) {
+    let Some((camcorder, player)) = players.iter().next() else {
+        return;
+    };
+    let night_vision = camcorder.night_vision_on(player);
+
+    for (mut projection, mut grading) in cameras.iter_mut() {
+        if let Projection::Perspective(perspective) = &mut *projection {
+            let target = camcorder.fov();
+            if accessibility.reduce_motion {
+                perspective.fov = target;
+            } else {
+                let ease = 1.0 - (-ZOOM_EASE * time.delta_seconds()).exp();
+                perspective.fov += (target - perspective.fov) * ease;
+            }
+        }
+
+        // only drops the colour, `vhs::NightVisionFilter` turns what's left green
+        let mut target = ColorGrading::default();
+        if night_vision {
+            target.global.exposure = 1.5;
+            target.global.post_saturation = 0.0;
+        }
+        if grading.global.post_saturation != target.global.post_saturation {
+            *grading = target;
+        }
+    }
+
+    for mut light in ir_lights.iter_mut() {
+        light.intensity = match night_vision {
+            true => IR_INTENSITY,
+            false => 0.0,
+        };
+    }
+}
@@ -0,0 +1,280 @@
+use crate::localization::Localization;
+use crate::navigation::MenuFocus;
+use crate::osd::{self, Beep};
+use crate::GameState;
+
+use super::camcorder::{Camcorder, CamcorderAction};
+use super::{GameSession, GameplayState, PlayerCamera};
+use bevy::prelude::*;
+use bevy::render::view::screenshot::ScreenshotManager;
+use bevy::utils::Duration;
+use bevy::window::PrimaryWindow;
+use bevy_egui::egui;
+use bevy_rapier3d::prelude::*;
+use fluent::FluentArgs;
+use leafwing_input_manager::prelude::*;
+use std::sync::{Arc, Mutex};
+
+/// Evidence further away than this is too small to make out on tape
+pub const CAPTURE_DISTANCE: f32 = 30.0;
+/// Captures a tape holds, the camcorder refuses to capture once it's full
+pub const MAX_CAPTURES: usize = 24;
+
+/// Snapshots of the camcorder's view, kept in a [`Gallery`] and checked for [`Evidence`]
+pub struct CapturePlugin;
+
+impl Plugin for CapturePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<EvidenceCaptured>()
+            .init_resource::<Gallery>()
+            .add_systems(
+                Update,
+                capture
+                    .run_if(in_state(GameState::Game).and_then(in_state(GameplayState::Playing))),
+            )
+            .add_systems(Update, collect_snapshots)
+            .add_systems(OnExit(GameState::Game), |mut commands: Commands| {
+                commands.insert_resource(Gallery::default());
+            });
+    }
+}
+
+/// Something worth catching on tape, named for objectives and the gallery
+#[derive(Component, Debug, Clone)]
+pub struct Evidence(pub String);
+
+/// Sent the first time a piece of evidence is captured
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct EvidenceCaptured(pub String);
+
+/// Every capture taken this session
+#[derive(Resource, Default)]
+pub struct Gallery {
+    pub captures: Vec<Capture>,
+    // screenshots arrive from the render world a frame or two later, by capture index
+    pending: Arc<Mutex<Vec<(usize, Image)>>>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Capture {
+    /// Snapshot of the screen, `None` until it arrives
+    pub image: Option<Handle<Image>>,
+    pub size: Vec2,
+    /// Names of the evidence in frame
+    pub evidence: Vec<String>,
+}
+
+impl Gallery {
+    /// Add a capture showing `evidence`, returning whatever of it hadn't been captured before
+    pub fn record(&mut self, evidence: Vec<String>) -> Vec<String> {
+        let new = evidence
+            .iter()
+            .filter(|name| !self.found().any(|found| found == name.as_str()))
+            .cloned()
+            .collect();
+        self.captures.push(Capture {
+            evidence,
+            ..default()
+        });
+        new
+    }
+
+    /// Every piece of evidence captured so far, once each
+    pub fn found(&self) -> impl Iterator<Item = &str> {
+        let mut seen = Vec::new();
+        self.captures
+            .iter()
+            .flat_map(|capture| capture.evidence.iter())
+            .filter(move |name| {
+                let first = !seen.contains(name);
+                seen.push(*name);
+                first
+            })
+            .map(String::as_str)
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.captures.len() >= MAX_CAPTURES
+    }
+}
+
+/// Whether a point in normalized device coordinates is within the frame
+pub fn in_frame(ndc: Vec3) -> bool {
+    ndc.x.abs() <= 1.0 && ndc.y.abs() <= 1.0 && (0.0..=1.0).contains(&ndc.z)
+}
+
+/// Whether nothing solid stands between `origin` and `target` at `position`, other than
+/// `exclude`. Sensors such as triggers and exits are invisible, so they never hide anything.
+pub fn unobstructed(
+    rapier: &RapierContext,
+    origin: Vec3,
+    target: Entity,
+    position: Vec3,
+    exclude: Entity,
+) -> bool {
+    let distance = origin.distance(position);
+    if distance <= 0.0 {
+        return true;
+    }
+    let filter = QueryFilter::default()
+        .exclude_sensors()
+        .exclude_collider(exclude);
+    let direction = (position - origin) / distance;
+    match rapier.cast_ray(origin, direction, distance, true, filter) {
+        Some((hit, _)) => hit == target,
+        None => true,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn capture(
+    mut commands: Commands,
+    mut gallery: ResMut<Gallery>,
+    mut captured: EventWriter<EvidenceCaptured>,
+    mut beeps: ResMut<Assets<Beep>>,
+    screenshots: Option<ResMut<ScreenshotManager>>,
+    rapier: Option<Res<RapierContext>>,
+    windows: Query<Entity, With<PrimaryWindow>>,
+    players: Query<(Entity, &Camcorder, &ActionState<CamcorderAction>)>,
+    cameras: Query<(&Camera, &GlobalTransform), With<PlayerCamera>>,
+    evidence: Query<(Entity, &Evidence, &GlobalTransform)>,
+) {
+    let Some((player, _, _)) = players.iter().find(|(_, camcorder, action_state)| {
+        camcorder.raised && action_state.just_pressed(&CamcorderAction::Capture)
+    }) else {
+        return;
+    };
+    let Ok((camera, view)) = cameras.get_single() else {
+        return;
+    };
+    if gallery.is_full() {
+        info!("Tape is full, {MAX_CAPTURES} captures");
+        return;
+    }
+
+    let origin = view.translation();
+    let seen = evidence
+        .iter()
+        .filter(|(entity, _, transform)| {
+            let position = transform.translation();
+            let distance = origin.distance(position);
+            if distance > CAPTURE_DISTANCE {
+                return false;
+            }
+            if !camera.world_to_ndc(view, position).is_some_and(in_frame) {
+                return false;
+            }
+            rapier.as_ref().map_or(true, |rapier| {
+                unobstructed(rapier, origin, *entity, position, player)
+            })
+        })
+        .map(|(_, evidence, _)| evidence.0.clone())
+        .collect::<Vec<_>>();
+    if !seen.is_empty() {
+        info!("Captured {}", seen.join(", "));
+    }
+    for name in gallery.record(seen) {
+        captured.send(EvidenceCaptured(name));
+    }
+
+    if let (Some(mut screenshots), Ok(window)) = (screenshots, windows.get_single()) {
+        let index = gallery.captures.len() - 1;
+        let pending = gallery.pending.clone();
+        let taken = screenshots.take_screenshot(window, move |image| {
+            pending.lock().unwrap().push((index, image));
+        });
+        if taken.is_err() {
+            warn!("A screenshot is already being taken, capture {index} has no image");
+        }
+    }
+
+    // shutter
+    commands.spawn((
+        AudioSourceBundle {
+            source: beeps.add(Beep {
+                frequency: 2000.0,
+                duration: Duration::from_millis(60),
+            }),
+            settings: PlaybackSettings::DESPAWN,
+        },
+        GameSession,
+    ));
+}
+
+fn collect_snapshots(mut gallery: ResMut<Gallery>, mut images: ResMut<Assets<Image>>) {
+    let pending = std::mem::take(&mut *gallery.pending.lock().unwrap());
+    for (index, image) in pending {
+        let size = image.size_f32();
+        let handle = images.add(image);
+        if let Some(capture) = gallery.captures.get_mut(index) {
+            capture.image = Some(handle);
+            capture.size = size;
+        }
+    }
+}
+
+/// Browse the gallery with OSD styling, returning `true` once the player goes back.
+/// `texture` is the egui texture of the capture at `selected`, if it has arrived.
+pub fn gallery_ui(
+    ui: &mut egui::Ui,
+    focus: &mut MenuFocus,
+    localization: &Localization,
+    gallery: &Gallery,
+    selected: &mut usize,
+    texture: Option<egui::TextureId>,
+) -> bool {
+    let text = |key: &str| localization.text(key);
+    ui.label(osd::text(text("gallery"), 64.0));
+
+    let found = gallery.found().count();
+    let args = FluentArgs::from_iter([("count", found)]);
+    ui.label(osd::text(
+        localization.text_with("gallery-evidence", &args),
+        24.0,
+    ));
+
+    ui.add_space(10.0);
+
+    match gallery.captures.get(*selected) {
+        Some(capture) => {
+            let names: Vec<_> = (1..=gallery.captures.len())
+                .map(|index| format!("{index}/{}", gallery.captures.len()))
+                .collect();
+            let names: Vec<_> = names.iter().map(String::as_str).collect();
+            focus.choice(ui, &text("gallery-capture"), selected, &names);
+
+            // fit the snapshot in whatever space is left above the caption and button
+            let space = ui.available_size() - egui::vec2(0.0, 120.0);
+            match texture {
+                Some(texture) if capture.size.x > 0.0 => {
+                    let size = egui::vec2(capture.size.x, capture.size.y);
+                    let scale = (space.x / size.x).min(space.y / size.y).min(1.0);
+                    ui.add(egui::Image::new(egui::load::SizedTexture::new(
+                        texture,
+                        size * scale,
+                    )));
+                }
+                _ => {
+                    ui.label(osd::text(text("gallery-developing"), 24.0));
+                }
+            }
+
+            let caption = match capture.evidence.is_empty() {
+                true => text("gallery-nothing").into_owned(),
+                false => capture
+                    .evidence
+                    .iter()
+                    .map(|name| text(&format!("evidence-{name}")))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            };
+            ui.label(osd::text(caption, 24.0));
+        }
+        None => {
+            ui.label(osd::text(text("gallery-empty"), 24.0));
+        }
+    }
+
+    let back = focus.button(ui, osd::text(text("settings-back"), 24.0));
+    back || focus.back()
+}
@@ -1,10 +1,159 @@
+use bevy::core_pipeline::Skybox;
 use bevy::prelude::*;
+use bevy::time::Real;
 use bevy_rapier3d::prelude::*;
+use leafwing_input_manager::prelude::*;
+use std::f32::consts::FRAC_PI_2;
+
+use super::{g3d, GameSession, PlayerCamera};
+use crate::accessibility::{Accessibility, Latch};
+use crate::debug::DebugTools;
+use crate::GameState;
+
+const FLY_SPEED: f32 = 6.0;
+const BOOST: f32 = 5.0;
+const MOUSE_SENSITIVITY: f32 = 0.003;
 
 pub struct Debug3DPlugin;
 
 impl Plugin for Debug3DPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(RapierDebugRenderPlugin::default());
+        app.add_plugins((
+            RapierDebugRenderPlugin::default(),
+            InputManagerPlugin::<FreeCameraAction>::default(),
+        ))
+        // also toggled by `DebugPlugin`, which inits it too
+        .init_resource::<DebugTools>()
+        .add_systems(
+            Update,
+            show_colliders.run_if(resource_changed::<DebugTools>),
+        )
+        .add_systems(
+            Update,
+            (
+                toggle_free_camera.run_if(resource_changed::<DebugTools>),
+                fly,
+            )
+                .chain()
+                .run_if(in_state(GameState::Game)),
+        )
+        .add_systems(OnExit(GameState::Game), |mut tools: ResMut<DebugTools>| {
+            tools.free_camera = false;
+        });
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, Reflect)]
+enum FreeCameraAction {
+    Move,
+    Look,
+    Up,
+    Down,
+    Boost,
+}
+
+impl Actionlike for FreeCameraAction {
+    fn input_control_kind(&self) -> InputControlKind {
+        match self {
+            Self::Move | Self::Look => InputControlKind::DualAxis,
+            _ => InputControlKind::Button,
+        }
+    }
+}
+
+#[derive(Component)]
+struct FreeCamera;
+
+fn show_colliders(tools: Res<DebugTools>, mut context: ResMut<DebugRenderContext>) {
+    context.enabled = tools.colliders;
+}
+
+// Swaps between the player's camera and a free one starting from the same view,
+// copying the level's skybox and fog so the scene looks the same
+fn toggle_free_camera(
+    mut commands: Commands,
+    tools: Res<DebugTools>,
+    free_cameras: Query<Entity, With<FreeCamera>>,
+    views: Query<(Entity, &GlobalTransform), With<PlayerCamera>>,
+    looks: Query<(Option<&Skybox>, Option<&FogSettings>)>,
+    mut player_cameras: Query<&mut Camera, With<PlayerCamera>>,
+    mut player_actions: Query<&mut ActionState<g3d::Action>>,
+) {
+    let active = !free_cameras.is_empty();
+    if tools.free_camera == active {
+        return;
+    }
+
+    for entity in &free_cameras {
+        commands.entity(entity).despawn_recursive();
+    }
+    for mut camera in player_cameras.iter_mut() {
+        camera.is_active = !tools.free_camera;
+    }
+    for (view, transform) in views.iter().filter(|_| tools.free_camera) {
+        let (skybox, fog) = looks.get(view).unwrap_or_default();
+        let mut free_camera = commands.spawn((
+            Camera3dBundle {
+                transform: transform.compute_transform().with_scale(Vec3::ONE),
+                ..Default::default()
+            },
+            InputManagerBundle::<FreeCameraAction> {
+                action_state: ActionState::default(),
+                input_map: InputMap::default()
+                    .with_dual_axis(FreeCameraAction::Move, KeyboardVirtualDPad::WASD)
+                    .with_dual_axis(FreeCameraAction::Move, GamepadStick::LEFT)
+                    .with_dual_axis(FreeCameraAction::Look, MouseMove::default())
+                    .with(FreeCameraAction::Up, KeyCode::KeyE)
+                    .with(FreeCameraAction::Down, KeyCode::KeyQ)
+                    .with(FreeCameraAction::Boost, KeyCode::ShiftLeft),
+            },
+            FreeCamera,
+            Name::new("free camera"),
+            GameSession,
+        ));
+        if let Some(skybox) = skybox {
+            free_camera.insert(skybox.clone());
+        }
+        if let Some(fog) = fog {
+            free_camera.insert(fog.clone());
+        }
+    }
+
+    // the player shares the gamepad sticks, keep them still while flying
+    for mut action_state in player_actions.iter_mut() {
+        match tools.free_camera {
+            true => action_state.disable(),
+            false => action_state.enable(),
+        }
+    }
+}
+
+fn fly(
+    time: Res<Time<Real>>,
+    accessibility: Res<Accessibility>,
+    mut boost: Local<Latch>,
+    mut query: Query<(&mut Transform, &ActionState<FreeCameraAction>), With<FreeCamera>>,
+) {
+    for (mut transform, action_state) in query.iter_mut() {
+        let look = action_state.axis_pair(&FreeCameraAction::Look) * MOUSE_SENSITIVITY;
+        let (mut yaw, mut pitch, _) = transform.rotation.to_euler(EulerRot::YXZ);
+        yaw -= look.x;
+        pitch = (pitch - look.y).clamp(-FRAC_PI_2 + 0.01, FRAC_PI_2 - 0.01);
+        transform.rotation = Quat::from_euler(EulerRot::YXZ, yaw, pitch, 0.0);
+
+        let input = action_state.clamped_axis_pair(&FreeCameraAction::Move);
+        let mut direction = transform.forward() * input.y + transform.right() * input.x;
+        if action_state.pressed(&FreeCameraAction::Up) {
+            direction += Vec3::Y;
+        }
+        if action_state.pressed(&FreeCameraAction::Down) {
+            direction -= Vec3::Y;
+        }
+        let mut speed = FLY_SPEED;
+        if boost.active(&accessibility, action_state, &FreeCameraAction::Boost) {
+            speed *= BOOST;
+        }
+        // real time so it keeps flying while the game is paused or slowed down
+        transform.translation += direction * speed * time.delta_seconds();
     }
 }
@@ -1,80 +1,359 @@
+use crate::accessibility::{Accessibility, Flashing};
+use crate::localization::{Localization, LocalizedText};
+#[cfg(debug_assertions)]
+use crate::warn_cardinality;
 use crate::GameState;
 
-use super::{despawn_screen, GameplayState};
+use super::objectives::{ObjectiveChanged, ObjectiveState};
+use super::{GameSession, GameplayState, Player, PlayerCamera, MAX_ZOOM};
 use bevy::prelude::*;
+use bevy::time::Stopwatch;
+use bevy::window::{PrimaryWindow, WindowResized};
+use fluent::FluentArgs;
+use std::collections::VecDeque;
+use std::f32::consts::FRAC_PI_4;
 
 #[derive(Component, Default)]
-pub(super) struct Vhs {
+struct Vhs {
     play_flash: Timer,
 }
 
 #[derive(Component, Default)]
-pub(super) struct Timestamp {}
+struct Timestamp {
+    // time recorded on this tape, restarts with every session
+    elapsed: Stopwatch,
+}
+
+/// Area the viewfinder is drawn in, everything in it is laid out relative to this
+#[derive(Component)]
+struct Viewfinder;
+
+/// HUD text, scaled along with the viewfinder from its font size at [`REFERENCE_HEIGHT`]
+#[derive(Component)]
+struct HudText(f32);
+
+#[derive(Component)]
+struct RecDot(Timer);
+
+#[derive(Component)]
+struct BatterySegment(usize);
+
+#[derive(Component)]
+struct LowBattery(Timer);
+
+/// Objective updates, shown one after the other in the middle of the viewfinder
+#[derive(Component)]
+struct Notification {
+    queue: VecDeque<String>,
+    shown: Timer,
+}
+
+#[derive(Component)]
+struct ZoomMarker;
+
+#[derive(Component)]
+struct ZoomLabel;
+
+#[derive(Component)]
+struct TapeCounter;
+
+const HUD_FONT_SIZE: f32 = 60.0;
+const SMALL_FONT_SIZE: f32 = 36.0;
+// localization keys of the mode shown in the corner
+const PLAY_LABEL: &str = "hud-play";
+const PAUSE_LABEL: &str = "hud-pause";
+/// Window height the HUD's font sizes are picked for
+pub const REFERENCE_HEIGHT: f32 = 720.0;
+/// Widest the viewfinder gets, wider windows leave the HUD centred instead of stretched out
+pub const VIEWFINDER_ASPECT: f32 = 16.0 / 9.0;
+const BATTERY_SEGMENTS: usize = 4;
+/// Battery percentage below which the viewfinder warns about it
+pub const LOW_BATTERY: f32 = 20.0;
+// a standard play T-120 cassette
+const TAPE_SECS: u32 = 120 * 60;
+/// Seconds each objective notification stays on screen
+const NOTIFICATION_SECS: f32 = 3.0;
 
 pub struct G2dPlugin;
 
 impl Plugin for G2dPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(GameState::Game), setup)
+        // sent by `ObjectivesPlugin`, the HUD stays quiet without it
+        app.add_event::<ObjectiveChanged>()
+            .add_systems(OnEnter(GameState::Game), (setup, apply_layout).chain())
             .add_systems(
                 Update,
-                update_vhs_play
-                    .run_if(in_state(GameState::Game).and_then(in_state(GameplayState::Playing))),
+                apply_layout.run_if(in_state(GameState::Game).and_then(
+                    resource_changed::<Accessibility>.or_else(on_event::<WindowResized>()),
+                )),
             )
             .add_systems(
                 Update,
-                update_vhs_timer
+                (
+                    update_vhs_play,
+                    update_vhs_timer,
+                    update_rec,
+                    update_battery,
+                    update_zoom,
+                )
                     .run_if(in_state(GameState::Game).and_then(in_state(GameplayState::Playing))),
             )
-            .add_systems(OnExit(GameState::Game), (despawn_screen::<OnGame2DScreen>,));
+            .add_systems(Update, notify.run_if(in_state(GameState::Game)))
+            .add_systems(OnEnter(GameplayState::Paused), show_mode(PAUSE_LABEL))
+            .add_systems(OnExit(GameplayState::Paused), show_mode(PLAY_LABEL));
+
+        // see `GamePlugin`
+        #[cfg(debug_assertions)]
+        app.add_systems(
+            Update,
+            (
+                warn_cardinality::<Vhs>(1..=usize::MAX),
+                warn_cardinality::<Timestamp>(1..=usize::MAX),
+            )
+                .run_if(in_state(GameState::Game)),
+        );
     }
 }
 
-#[derive(Component)]
-struct OnGame2DScreen;
+/// Where the viewfinder sits in a window and how much its text is scaled
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ViewfinderLayout {
+    pub position: Vec2,
+    pub size: Vec2,
+    pub scale: f32,
+}
 
-fn setup(mut commands: Commands) {
-    // timestamp
-    commands.spawn((
-        TextBundle::from_section(
-            "00:00:00",
-            TextStyle {
-                font_size: 60.0,
-                color: Color::WHITE,
-                ..Default::default()
-            },
-        )
-        .with_style(Style {
-            position_type: PositionType::Absolute,
-            bottom: Val::Px(5.0),
-            left: Val::Px(5.0),
+impl ViewfinderLayout {
+    /// Fill the window, up to [`VIEWFINDER_ASPECT`] wide
+    pub fn fit(window: Vec2) -> Self {
+        let size = Vec2::new(window.x.min(window.y * VIEWFINDER_ASPECT), window.y);
+        Self {
+            position: (window - size) / 2.0,
+            size,
+            scale: window.y / REFERENCE_HEIGHT,
+        }
+    }
+}
+
+fn hud_text(text: &str, size: f32) -> (TextBundle, HudText) {
+    let bundle = TextBundle::from_section(
+        text,
+        TextStyle {
+            font_size: size,
+            color: Color::WHITE,
             ..Default::default()
-        }),
-        Timestamp {},
-        OnGame2DScreen,
-    ));
+        },
+    );
+    (bundle, HudText(size))
+}
 
-    // play button
-    commands.spawn((
-        TextBundle::from_section(
-            "Play: \u{25B6}",
-            TextStyle {
-                font_size: 60.0,
-                color: Color::WHITE,
-                ..Default::default()
-            },
-        )
-        .with_style(Style {
+// Corner brackets of the viewfinder frame, `right` and `bottom` pick the corner
+fn frame_corner(right: bool, bottom: bool) -> NodeBundle {
+    let edge = Val::Percent(3.0);
+    let line = Val::Vh(0.5);
+    NodeBundle {
+        style: Style {
             position_type: PositionType::Absolute,
-            top: Val::Px(5.0),
-            left: Val::Px(5.0),
+            width: Val::Vh(6.0),
+            height: Val::Vh(6.0),
+            left: if right { Val::Auto } else { edge },
+            right: if right { edge } else { Val::Auto },
+            top: if bottom { Val::Auto } else { edge },
+            bottom: if bottom { edge } else { Val::Auto },
+            border: UiRect {
+                left: if right { Val::ZERO } else { line },
+                right: if right { line } else { Val::ZERO },
+                top: if bottom { Val::ZERO } else { line },
+                bottom: if bottom { line } else { Val::ZERO },
+            },
             ..Default::default()
-        }),
-        Vhs {
-            play_flash: Timer::from_seconds(0.5, TimerMode::Repeating),
         },
-        OnGame2DScreen,
-    ));
+        border_color: Color::WHITE.into(),
+        ..Default::default()
+    }
+}
+
+fn row(justify_content: JustifyContent) -> NodeBundle {
+    NodeBundle {
+        style: Style {
+            width: Val::Percent(100.0),
+            justify_content,
+            align_items: AlignItems::Center,
+            column_gap: Val::Vh(1.5),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+fn setup(mut commands: Commands) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    padding: UiRect::all(Val::Percent(5.0)),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::SpaceBetween,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            Viewfinder,
+            GameSession,
+        ))
+        .with_children(|parent| {
+            for (right, bottom) in [(false, false), (true, false), (false, true), (true, true)] {
+                parent.spawn(frame_corner(right, bottom));
+            }
+
+            parent
+                .spawn(row(JustifyContent::SpaceBetween))
+                .with_children(|top| {
+                    // play button
+                    top.spawn((
+                        hud_text("Play: \u{25B6}", HUD_FONT_SIZE),
+                        Vhs {
+                            play_flash: Timer::from_seconds(0.5, TimerMode::Repeating),
+                        },
+                        LocalizedText(PLAY_LABEL),
+                    ));
+
+                    top.spawn(row(JustifyContent::FlexEnd))
+                        .with_children(|indicators| {
+                            indicators.spawn((
+                                hud_text("\u{25CF}", SMALL_FONT_SIZE),
+                                RecDot(Timer::from_seconds(1.0, TimerMode::Repeating)),
+                            ));
+                            indicators.spawn((
+                                hud_text("REC", SMALL_FONT_SIZE),
+                                LocalizedText("hud-rec"),
+                            ));
+
+                            // battery, a cell with a segment per quarter of charge
+                            indicators
+                                .spawn(NodeBundle {
+                                    style: Style {
+                                        margin: UiRect::left(Val::Vh(2.0)),
+                                        padding: UiRect::all(Val::Vh(0.4)),
+                                        column_gap: Val::Vh(0.4),
+                                        border: UiRect::all(Val::Vh(0.4)),
+                                        ..Default::default()
+                                    },
+                                    border_color: Color::WHITE.into(),
+                                    ..Default::default()
+                                })
+                                .with_children(|cell| {
+                                    for index in 0..BATTERY_SEGMENTS {
+                                        cell.spawn((
+                                            NodeBundle {
+                                                style: Style {
+                                                    width: Val::Vh(1.2),
+                                                    height: Val::Vh(2.6),
+                                                    ..Default::default()
+                                                },
+                                                background_color: Color::WHITE.into(),
+                                                ..Default::default()
+                                            },
+                                            BatterySegment(index),
+                                        ));
+                                    }
+                                });
+                            // the cell's terminal
+                            indicators.spawn(NodeBundle {
+                                style: Style {
+                                    width: Val::Vh(0.6),
+                                    height: Val::Vh(1.6),
+                                    margin: UiRect::left(Val::Vh(-1.5)),
+                                    ..Default::default()
+                                },
+                                background_color: Color::WHITE.into(),
+                                ..Default::default()
+                            });
+                        });
+                });
+
+            let mut middle = row(JustifyContent::Center);
+            middle.style.flex_direction = FlexDirection::Column;
+            middle.style.row_gap = Val::Vh(1.5);
+            parent.spawn(middle).with_children(|middle| {
+                let (text, size) = hud_text("", SMALL_FONT_SIZE);
+                middle.spawn((
+                    TextBundle {
+                        visibility: Visibility::Hidden,
+                        ..text
+                    },
+                    size,
+                    Notification {
+                        queue: VecDeque::new(),
+                        shown: Timer::from_seconds(NOTIFICATION_SECS, TimerMode::Once),
+                    },
+                ));
+
+                let (text, size) = hud_text("BATTERY LOW", SMALL_FONT_SIZE);
+                middle.spawn((
+                    TextBundle {
+                        visibility: Visibility::Hidden,
+                        ..text
+                    },
+                    size,
+                    LocalizedText("hud-low-battery"),
+                    LowBattery(Timer::from_seconds(0.5, TimerMode::Repeating)),
+                ));
+            });
+
+            parent
+                .spawn(row(JustifyContent::SpaceBetween))
+                .with_children(|bottom| {
+                    // timestamp
+                    bottom.spawn((hud_text("00:00:00", HUD_FONT_SIZE), Timestamp::default()));
+
+                    // zoom, from wide to telephoto
+                    bottom
+                        .spawn(row(JustifyContent::Center))
+                        .with_children(|zoom| {
+                            zoom.spawn((
+                                hud_text("W", SMALL_FONT_SIZE),
+                                LocalizedText("hud-zoom-wide"),
+                            ));
+                            zoom.spawn(NodeBundle {
+                                style: Style {
+                                    width: Val::Vh(20.0),
+                                    height: Val::Vh(0.5),
+                                    ..Default::default()
+                                },
+                                background_color: Color::WHITE.with_alpha(0.5).into(),
+                                ..Default::default()
+                            })
+                            .with_children(|track| {
+                                track.spawn((
+                                    NodeBundle {
+                                        style: Style {
+                                            position_type: PositionType::Absolute,
+                                            width: Val::Vh(0.6),
+                                            height: Val::Vh(2.5),
+                                            top: Val::Vh(-1.0),
+                                            left: Val::Percent(0.0),
+                                            ..Default::default()
+                                        },
+                                        background_color: Color::WHITE.into(),
+                                        ..Default::default()
+                                    },
+                                    ZoomMarker,
+                                ));
+                            });
+                            zoom.spawn((
+                                hud_text("T", SMALL_FONT_SIZE),
+                                LocalizedText("hud-zoom-tele"),
+                            ));
+                            zoom.spawn((hud_text("x1.0", SMALL_FONT_SIZE), ZoomLabel));
+                        });
+
+                    bottom.spawn((hud_text("SP 2:00:00", SMALL_FONT_SIZE), TapeCounter));
+                });
+        });
 
     // spawn 2D overlay
     commands.spawn((
@@ -86,28 +365,217 @@ fn setup(mut commands: Commands) {
             },
             ..Default::default()
         },
-        OnGame2DScreen,
+        GameSession,
     ));
 }
 
-fn update_vhs_play(time: Res<Time<Real>>, mut query: Query<(&mut Vhs, &mut Text)>) {
-    let (mut vhs, mut text) = query.single_mut();
-    // toggle visibility on repeat
-    if vhs.play_flash.tick(time.delta()).just_finished() {
-        text.sections[0].style.color = if text.sections[0].style.color == Color::WHITE {
-            Color::NONE
-        } else {
-            Color::WHITE
+fn update_vhs_play(
+    time: Res<Time>,
+    accessibility: Res<Accessibility>,
+    mut query: Query<(&mut Vhs, &mut Text)>,
+) {
+    for (mut vhs, mut text) in query.iter_mut() {
+        if accessibility.flashing != Flashing::On {
+            text.sections[0].style.color = Color::WHITE;
+            continue;
+        }
+        // toggle visibility on repeat
+        if vhs.play_flash.tick(time.delta()).just_finished() {
+            text.sections[0].style.color = if text.sections[0].style.color == Color::WHITE {
+                Color::NONE
+            } else {
+                Color::WHITE
+            };
+        }
+    }
+}
+
+fn update_vhs_timer(
+    time: Res<Time>,
+    localization: Res<Localization>,
+    mut query: Query<(&mut Timestamp, &mut Text), Without<TapeCounter>>,
+    mut counters: Query<&mut Text, With<TapeCounter>>,
+) {
+    for (mut timestamp, mut text) in query.iter_mut() {
+        let elapsed = timestamp.elapsed.tick(time.delta()).elapsed_secs() as u32;
+        text.sections[0].value = format!(
+            "{:02}:{:02}:{:02}",
+            elapsed / 3600,
+            elapsed / 60 % 60,
+            elapsed % 60
+        );
+
+        // tape left, as a camcorder counts it
+        let remaining = TAPE_SECS.saturating_sub(elapsed);
+        let left = format!(
+            "{}:{:02}:{:02}",
+            remaining / 3600,
+            remaining / 60 % 60,
+            remaining % 60
+        );
+        let args = FluentArgs::from_iter([("time", left)]);
+        let value = localization.text_with("hud-tape-left", &args);
+        for mut counter in counters.iter_mut() {
+            let section = &mut counter.sections[0];
+            section.value = value.to_string();
+            section.style.font = localization.font().cloned().unwrap_or_default();
+        }
+    }
+}
+
+// The dot blinks once a second while recording
+fn update_rec(
+    time: Res<Time>,
+    accessibility: Res<Accessibility>,
+    mut query: Query<(&mut RecDot, &mut Text, &mut Visibility)>,
+) {
+    for (mut dot, mut text, mut visibility) in query.iter_mut() {
+        text.sections[0].style.color = accessibility.danger();
+        dot.0.tick(time.delta());
+        let shown = accessibility.flashing != Flashing::On || dot.0.fraction() < 0.5;
+        visibility.set_if_neq(match shown {
+            true => Visibility::Inherited,
+            false => Visibility::Hidden,
+        });
+    }
+}
+
+fn update_battery(
+    time: Res<Time>,
+    accessibility: Res<Accessibility>,
+    players: Query<&Player>,
+    mut segments: Query<(&BatterySegment, &mut BackgroundColor)>,
+    mut warnings: Query<(&mut LowBattery, &mut LocalizedText, &mut Visibility)>,
+) {
+    let Some(player) = players.iter().next() else {
+        return;
+    };
+    let low = player.battery < LOW_BATTERY;
+
+    let charged = (player.battery / 100.0 * BATTERY_SEGMENTS as f32).ceil() as usize;
+    for (segment, mut background) in segments.iter_mut() {
+        let color = match (segment.0 < charged, low) {
+            (true, true) => accessibility.danger(),
+            (true, false) => Color::WHITE,
+            (false, _) => Color::NONE,
         };
+        background.set_if_neq(color.into());
+    }
+
+    for (mut warning, mut key, mut visibility) in warnings.iter_mut() {
+        key.set_if_neq(LocalizedText(match player.battery <= 0.0 {
+            true => "hud-battery-empty",
+            false => "hud-low-battery",
+        }));
+        warning.0.tick(time.delta());
+        let blink = accessibility.flashing != Flashing::On || warning.0.fraction() < 0.5;
+        visibility.set_if_neq(match low && blink {
+            true => Visibility::Inherited,
+            false => Visibility::Hidden,
+        });
     }
 }
 
-fn update_vhs_timer(time: Res<Time>, mut query: Query<(&mut Timestamp, &mut Text)>) {
-    let (_, mut text) = query.single_mut();
-    text.sections[0].value = format!(
-        "{:02}:{:02}:{:02}",
-        time.elapsed_seconds() as u32 / 3600,
-        time.elapsed_seconds() as u32 / 60 % 60,
-        time.elapsed_seconds() as u32 % 60
-    );
+fn notify(
+    time: Res<Time>,
+    localization: Res<Localization>,
+    mut changes: EventReader<ObjectiveChanged>,
+    mut query: Query<(&mut Notification, &mut Text, &mut Visibility)>,
+) {
+    let Ok((mut notification, mut text, mut visibility)) = query.get_single_mut() else {
+        return;
+    };
+    for change in changes.read() {
+        let key = match change.state {
+            ObjectiveState::Hidden => continue,
+            ObjectiveState::Active => "objective-new",
+            ObjectiveState::Completed => "objective-completed",
+            ObjectiveState::Failed => "objective-failed",
+        };
+        let objective = localization.text(&format!("objective-{}", change.id));
+        let args = FluentArgs::from_iter([("objective", objective)]);
+        let message = localization.text_with(key, &args).into_owned();
+        notification.queue.push_back(message);
+    }
+
+    notification.shown.tick(time.delta());
+    if !notification.shown.finished() {
+        return;
+    }
+    match notification.queue.pop_front() {
+        Some(message) => {
+            let section = &mut text.sections[0];
+            section.value = message;
+            section.style.font = localization.font().cloned().unwrap_or_default();
+            notification.shown.reset();
+            *visibility = Visibility::Inherited;
+        }
+        None => {
+            visibility.set_if_neq(Visibility::Hidden);
+        }
+    }
+}
+
+/// How far the camera is zoomed in, from its field of view
+pub fn zoom_level(projection: &Projection) -> f32 {
+    match projection {
+        Projection::Perspective(perspective) => FRAC_PI_4 / perspective.fov,
+        Projection::Orthographic(_) => 1.0,
+    }
+}
+
+fn update_zoom(
+    cameras: Query<&Projection, (With<PlayerCamera>, Changed<Projection>)>,
+    mut markers: Query<&mut Style, With<ZoomMarker>>,
+    mut labels: Query<&mut Text, With<ZoomLabel>>,
+) {
+    let Some(projection) = cameras.iter().next() else {
+        return;
+    };
+    let zoom = zoom_level(projection);
+    let along = ((zoom - 1.0) / (MAX_ZOOM - 1.0)).clamp(0.0, 1.0);
+    for mut style in markers.iter_mut() {
+        style.left = Val::Percent(along * 100.0);
+    }
+    for mut text in labels.iter_mut() {
+        text.sections[0].value = format!("x{zoom:.1}");
+    }
+}
+
+// Like a camcorder, the mode is shown steadily while paused
+fn show_mode(label: &'static str) -> impl FnMut(Query<(&mut LocalizedText, &mut Text), With<Vhs>>) {
+    move |mut query| {
+        for (mut key, mut text) in query.iter_mut() {
+            key.set_if_neq(LocalizedText(label));
+            text.sections[0].style.color = Color::WHITE;
+        }
+    }
+}
+
+// Fit the viewfinder to the window and size its text for it
+fn apply_layout(
+    accessibility: Res<Accessibility>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut viewfinders: Query<&mut Style, With<Viewfinder>>,
+    mut texts: Query<(&HudText, &mut Text, &mut BackgroundColor)>,
+) {
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    let layout = ViewfinderLayout::fit(window.size());
+
+    for mut style in viewfinders.iter_mut() {
+        style.left = Val::Px(layout.position.x);
+        style.top = Val::Px(layout.position.y);
+        style.width = Val::Px(layout.size.x);
+        style.height = Val::Px(layout.size.y);
+    }
+
+    for (size, mut text, mut background) in texts.iter_mut() {
+        text.sections[0].style.font_size = size.0 * layout.scale * accessibility.text_scale;
+        *background = match accessibility.high_contrast {
+            true => Color::BLACK.with_alpha(0.8).into(),
+            false => Color::NONE.into(),
+        };
+    }
 }
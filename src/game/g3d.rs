@@ -1,11 +1,14 @@
+use crate::accessibility::{Accessibility, Flashing};
+use crate::assets::AudioAssets;
+use crate::rng::GameRng;
+use crate::skybox::SkyboxFlash;
 use crate::GameState;
 
-use super::{despawn_screen, AudioAssets, GameplayState, GltfAssets, Player, TextureAssets};
-use bevy::asset::LoadState;
-use bevy::core_pipeline::Skybox;
-use bevy::gltf::Gltf;
+use super::camcorder::{Camcorder, CamcorderAction, IrLight, NIGHT_VISION_DRAIN};
+use super::house::HouseItem;
+use super::objectives::ObjectiveEvent;
+use super::{GameSession, GameplayState, Inventory, Player, PlayerCamera, BATTERY_DRAIN};
 use bevy::prelude::*;
-use bevy::render::render_resource::{TextureViewDescriptor, TextureViewDimension};
 use bevy_rapier3d::prelude::*;
 use leafwing_input_manager::prelude::*;
 use rand::Rng;
@@ -19,10 +22,16 @@ impl Plugin for G3dPlugin {
             InputManagerPlugin::<Action>::default(),
             RapierPhysicsPlugin::<NoUserData>::default(),
         ))
-        .add_systems(OnEnter(GameState::Game), (setup, spawn_house))
+        .add_systems(OnEnter(GameState::Game), setup)
         .add_systems(
             Update,
-            (camera_rotation, light_flicker)
+            (
+                camera_rotation,
+                drain_battery,
+                light_flicker,
+                lightning,
+                pick_up,
+            )
                 .run_if(in_state(GameState::Game))
                 .run_if(in_state(GameplayState::Playing)),
         )
@@ -34,21 +43,23 @@ impl Plugin for G3dPlugin {
                     .and_then(in_state(GameplayState::Playing))
                     .and_then(intro_finished),
             ),
-        )
-        .add_systems(OnExit(GameState::Game), (despawn_screen::<OnGame3DScreen>,));
+        );
     }
 }
 
 const PLAYER_INIT_LOCATION: Vec3 = Vec3::new(0.0, 0.0, 1000.0);
-
-#[derive(Component)]
-struct OnGame3DScreen;
+/// Items closer than this to the player are picked up
+const PICK_UP_DISTANCE: f32 = 1.5;
+/// Average flashlight flickers per second
+const FLICKER_RATE: f32 = 1.2;
+/// Average lightning strikes per second
+const LIGHTNING_RATE: f32 = 0.03;
 
 #[derive(Component)]
 struct Intro;
 
 #[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, Reflect)]
-enum Action {
+pub enum Action {
     Move,
     Look,
 }
@@ -64,95 +75,26 @@ impl Actionlike for Action {
 }
 
 fn setup(
-    asset_server: Res<AssetServer>,
     mut commands: Commands,
-    mut images: ResMut<Assets<Image>>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
+    audio_sources: Res<Assets<AudioSource>>,
     sounds: Res<AudioAssets>,
-    textures: ResMut<TextureAssets>,
 ) {
-    commands.spawn((
-        AudioBundle {
-            source: sounds.intro.clone(),
-            settings: PlaybackSettings::DESPAWN,
-        },
-        Name::new("intro"),
-        Intro,
-        OnGame3DScreen,
-    ));
-
-    commands.spawn((
-        DirectionalLightBundle {
-            directional_light: DirectionalLight {
-                illuminance: 320.0,
-                ..default()
+    // a missing intro would otherwise hold the player in place forever
+    if audio_sources.contains(&sounds.intro) {
+        commands.spawn((
+            AudioBundle {
+                source: sounds.intro.clone(),
+                settings: PlaybackSettings::DESPAWN,
             },
-            transform: Transform::from_xyz(0.0, 2.0, 0.0)
-                .with_rotation(Quat::from_rotation_x(-PI / 4.)),
-            ..default()
-        },
-        Name::new("moon"),
-    ));
-
-    if asset_server.load_state(&textures.skybox) == LoadState::Loaded {
-        let image = images.get_mut(&textures.skybox).unwrap();
-        if image.texture_descriptor.array_layer_count() == 1 {
-            image.reinterpret_stacked_2d_as_array(image.height() / image.width());
-            image.texture_view_descriptor = Some(TextureViewDescriptor {
-                dimension: Some(TextureViewDimension::Cube),
-                ..default()
-            });
-        }
+            Name::new("intro"),
+            Intro,
+            GameSession,
+        ));
+    } else {
+        warn!("Intro audio is missing, skipping intro");
     }
 
-    // spawn ground
-    commands.spawn((
-        RigidBody::Fixed,
-        Collider::cuboid(100.0, 0.1, 100.0),
-        PbrBundle {
-            mesh: meshes.add(Mesh::from(Cuboid::new(100.0, 0.1, 100.0))),
-            material: materials.add(StandardMaterial {
-                base_color: Color::srgb(0.5, 0.5, 0.5),
-                unlit: true,
-                ..default()
-            }),
-            transform: Transform::from_xyz(0.0, -0.1, 0.0),
-            ..Default::default()
-        },
-        Name::new("ground"),
-        OnGame3DScreen,
-    ));
-
-    // spawn box around world
-    commands.spawn((
-        RigidBody::Fixed,
-        Collider::cuboid(20.0, 10.0, 1.0),
-        TransformBundle::from(Transform::from_xyz(0.0, 0.0, 20.0)),
-        Name::new("back wall"),
-        OnGame3DScreen,
-    ));
-    commands.spawn((
-        RigidBody::Fixed,
-        Collider::cuboid(20.0, 10.0, 1.0),
-        TransformBundle::from(Transform::from_xyz(0.0, 0.0, -20.0)),
-        Name::new("front wall"),
-        OnGame3DScreen,
-    ));
-    commands.spawn((
-        RigidBody::Fixed,
-        Collider::cuboid(1.0, 10.0, 20.0),
-        TransformBundle::from(Transform::from_xyz(20.0, 0.0, 0.0)),
-        Name::new("right wall"),
-        OnGame3DScreen,
-    ));
-    commands.spawn((
-        RigidBody::Fixed,
-        Collider::cuboid(1.0, 10.0, 20.0),
-        TransformBundle::from(Transform::from_xyz(-20.0, 0.0, 0.0)),
-        Name::new("left wall"),
-        OnGame3DScreen,
-    ));
+    // moon, ground and boundary walls are spawned by the level
 
     // spawn flashlight with camera
     commands
@@ -170,9 +112,8 @@ fn setup(
                 },
                 ..Default::default()
             },
-            Player {
-                flashlight_flicker: Timer::from_seconds(0.1, TimerMode::Once),
-            },
+            Player::default(),
+            Inventory::default(),
             RigidBody::Dynamic,
             LockedAxes::ROTATION_LOCKED_X | LockedAxes::ROTATION_LOCKED_Z,
             Collider::capsule_y(0.5, 0.2),
@@ -193,8 +134,13 @@ fn setup(
                     .with_dual_axis(Action::Move, GamepadStick::LEFT)
                     .with_dual_axis(Action::Look, GamepadStick::RIGHT),
             },
+            Camcorder::default(),
+            InputManagerBundle::<CamcorderAction> {
+                action_state: ActionState::default(),
+                input_map: CamcorderAction::input_map(),
+            },
             Name::new("player"),
-            OnGame3DScreen,
+            GameSession,
         ))
         .with_children(|parent| {
             parent.spawn((
@@ -202,45 +148,30 @@ fn setup(
                     transform: Transform::from_xyz(0.0, 0.7, 0.0),
                     ..Default::default()
                 },
-                Skybox {
-                    image: textures.skybox.clone(),
-                    brightness: 100.0,
-                },
+                // skybox and fog are added by the level
                 // AtmosphereCamera::default(),
-                FogSettings {
-                    color: Color::srgba(0.05, 0.05, 0.05, 1.0),
-                    falloff: FogFalloff::Exponential { density: 0.15 },
+                PlayerCamera,
+            ));
+            // wide and off until night vision turns it on
+            parent.spawn((
+                SpotLightBundle {
+                    spot_light: SpotLight {
+                        intensity: 0.0,
+                        range: 40.0,
+                        outer_angle: 0.6,
+                        ..Default::default()
+                    },
                     ..Default::default()
                 },
+                IrLight,
             ));
         });
 
-    commands.insert_resource(AmbientLight {
-        color: Color::srgb_u8(210, 220, 240),
-        brightness: 1.0,
-    });
-}
-
-fn spawn_house(mut commands: Commands, assets: Res<GltfAssets>, assets_gltf: Res<Assets<Gltf>>) {
-    if let Some(gltf) = assets_gltf.get(&assets.house.clone()) {
-        commands.spawn((
-            SceneBundle {
-                scene: gltf.scenes[0].clone(),
-                transform: Transform::from_xyz(0.0, 0.0, 0.0).looking_at(Vec3::Z, Vec3::Y),
-                ..Default::default()
-            },
-            AsyncSceneCollider {
-                shape: Some(ComputedColliderShape::TriMesh),
-                ..Default::default()
-            },
-            OnGame3DScreen,
-        ));
-
-        commands.spawn((
-            SpatialBundle::from_transform(Transform::from_translation(PLAYER_INIT_LOCATION)),
-            SpatialListener::new(4.0),
-        ));
-    }
+    commands.spawn((
+        SpatialBundle::from_transform(Transform::from_translation(PLAYER_INIT_LOCATION)),
+        SpatialListener::new(4.0),
+        GameSession,
+    ));
 }
 
 fn movement(
@@ -284,15 +215,86 @@ fn camera_rotation(
     }
 }
 
-fn light_flicker(time: Res<Time>, mut query: Query<(&mut Player, &mut SpotLight)>) {
-    let (mut player, mut light) = query.single_mut();
-    player.flashlight_flicker.tick(time.delta());
-    if rand::thread_rng().gen_range(0..50) == 0 {
-        light.intensity = 50.0;
-        player.flashlight_flicker.reset();
+fn drain_battery(time: Res<Time>, mut query: Query<(&mut Player, Option<&Camcorder>)>) {
+    for (mut player, camcorder) in query.iter_mut() {
+        let drain = match camcorder.is_some_and(|camcorder| camcorder.night_vision_on(&player)) {
+            true => BATTERY_DRAIN * NIGHT_VISION_DRAIN,
+            false => BATTERY_DRAIN,
+        };
+        player.battery = (player.battery - drain * time.delta_seconds()).max(0.0);
     }
-    if player.flashlight_flicker.finished() {
-        light.intensity = 200.0;
+}
+
+pub fn light_flicker(
+    time: Res<Time>,
+    mut rng: ResMut<GameRng>,
+    accessibility: Res<Accessibility>,
+    mut query: Query<(&mut Player, &mut SpotLight, Option<&Camcorder>)>,
+) {
+    let rng = rng.stream("flicker");
+    let (dimmed, rate) = match accessibility.flashing {
+        Flashing::On => (50.0, FLICKER_RATE),
+        Flashing::Limited => (150.0, FLICKER_RATE / 3.0),
+        Flashing::Off => (200.0, 0.0),
+    };
+    for (mut player, mut light, camcorder) in query.iter_mut() {
+        // night vision lights the way in infrared instead
+        let night_vision = camcorder.is_some_and(|camcorder| camcorder.night_vision_on(&player));
+        if player.battery <= 0.0 || night_vision {
+            light.intensity = 0.0;
+            continue;
+        }
+        player.flashlight_flicker.tick(time.delta());
+        // per second rather than per frame, so it flickers as often at any frame rate
+        if rng.gen_bool((rate * time.delta_seconds()).min(1.0) as f64) {
+            light.intensity = dimmed;
+            player.flashlight_flicker.reset();
+        }
+        if player.flashlight_flicker.finished() {
+            light.intensity = 200.0;
+        }
+    }
+}
+
+/// Pick up items the player walks up to, for objectives when `ObjectivesPlugin` is added
+pub(super) fn pick_up(
+    mut commands: Commands,
+    mut players: Query<(&GlobalTransform, &mut Inventory), With<Player>>,
+    items: Query<(Entity, &HouseItem, &GlobalTransform)>,
+    mut events: Option<ResMut<Events<ObjectiveEvent>>>,
+) {
+    for (player, mut inventory) in players.iter_mut() {
+        for (entity, item, transform) in items.iter() {
+            if player.translation().distance(transform.translation()) > PICK_UP_DISTANCE {
+                continue;
+            }
+            info!("Picked up {}", item.0);
+            inventory.0.push(item.0.clone());
+            commands.entity(entity).despawn_recursive();
+            if let Some(events) = &mut events {
+                events.send(ObjectiveEvent::ItemPickedUp(item.0.clone()));
+            }
+        }
+    }
+}
+
+fn lightning(
+    time: Res<Time>,
+    mut rng: ResMut<GameRng>,
+    accessibility: Res<Accessibility>,
+    mut query: Query<&mut SkyboxFlash>,
+) {
+    let rng = rng.stream("lightning");
+    let rate = match accessibility.flashing {
+        Flashing::On => LIGHTNING_RATE,
+        Flashing::Limited => LIGHTNING_RATE / 3.0,
+        Flashing::Off => return,
+    };
+    let chance = (rate * time.delta_seconds()).min(1.0) as f64;
+    for mut flash in query.iter_mut() {
+        if rng.gen_bool(chance) {
+            flash.trigger();
+        }
     }
 }
 
@@ -0,0 +1,321 @@
+use super::level::{LevelExit, LevelScoped};
+use super::objectives::ObjectiveTrigger;
+use super::GameSession;
+use bevy::gltf::Gltf;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::Deserialize;
+
+const WALL_THICKNESS: f32 = 0.2;
+const DOOR_WIDTH: f32 = 1.2;
+
+/// Grid offset of the neighbour on each side of a room: north, east, south, west
+const SIDES: [IVec2; 4] = [IVec2::NEG_Y, IVec2::X, IVec2::Y, IVec2::NEG_X];
+
+/// Assembles a level from modular room pieces instead of a single world, laid out from a seed
+#[derive(Deserialize, Debug, Clone)]
+pub struct LayoutConfig {
+    /// glTF file with `floor`, `wall` and `doorway` scenes, plus one scene per item.
+    /// Plain boxes are used for any piece it doesn't have
+    #[serde(default)]
+    pub pieces: Option<String>,
+    pub rooms: usize,
+    /// Width and depth of every room
+    pub room_size: f32,
+    pub wall_height: f32,
+    /// Each item is placed once, in a room other than the first
+    #[serde(default)]
+    pub items: Vec<String>,
+    /// Number of scare triggers, placed like items
+    #[serde(default)]
+    pub scares: usize,
+    /// Level the exit in the room furthest from the first one leads to
+    #[serde(default)]
+    pub exit: Option<String>,
+}
+
+/// Seed for generated houses, a new one is picked every time a house is entered when `None`
+#[derive(Resource, Default)]
+pub struct HouseSeed(pub Option<u64>);
+
+/// Seed of the house the player is in, to share or reproduce it
+#[derive(Resource, Debug)]
+pub struct CurrentHouse(pub u64);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HouseLayout {
+    pub seed: u64,
+    /// The first room is where the player enters
+    pub rooms: Vec<Room>,
+    pub placements: Vec<Placement>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Room {
+    pub cell: IVec2,
+    /// Whether there is a door on the north, east, south and west side
+    pub doors: [bool; 4],
+    /// Number of doors between this room and the first one
+    pub depth: usize,
+}
+
+impl Room {
+    /// Grid cell of the room next to this one on `side`, an index into [`Room::doors`]
+    pub fn neighbour(&self, side: usize) -> IVec2 {
+        self.cell + SIDES[side]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Placement {
+    pub room: usize,
+    /// Position on the floor relative to the centre of the room, as a fraction of its size
+    pub offset: Vec2,
+    pub kind: PlacementKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlacementKind {
+    Item(String),
+    Scare,
+    Exit(String),
+}
+
+/// Marks where an item was placed in a generated house
+#[derive(Component)]
+pub struct HouseItem(pub String);
+
+/// Trigger volume placed in a generated house for a scare to play out in
+#[derive(Component)]
+pub struct ScareTrigger;
+
+/// Lay out a house by growing it one room at a time from a random existing room,
+/// so every room is reachable from the first
+pub fn generate(seed: u64, config: &LayoutConfig) -> HouseLayout {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut rooms = vec![Room {
+        cell: IVec2::ZERO,
+        doors: [false; 4],
+        depth: 0,
+    }];
+
+    while rooms.len() < config.rooms.max(1) {
+        let from = rng.gen_range(0..rooms.len());
+        let side = rng.gen_range(0..SIDES.len());
+        let cell = rooms[from].neighbour(side);
+        if rooms.iter().any(|room| room.cell == cell) {
+            continue;
+        }
+        rooms[from].doors[side] = true;
+        let mut doors = [false; 4];
+        doors[(side + 2) % 4] = true;
+        rooms.push(Room {
+            cell,
+            doors,
+            depth: rooms[from].depth + 1,
+        });
+    }
+
+    let mut placements = Vec::new();
+    if let Some(to) = &config.exit {
+        // ties go to the earliest room so the layout stays stable
+        let furthest = (0..rooms.len())
+            .rev()
+            .max_by_key(|room| rooms[*room].depth)
+            .unwrap_or(0);
+        placements.push(Placement {
+            room: furthest,
+            offset: Vec2::ZERO,
+            kind: PlacementKind::Exit(to.clone()),
+        });
+    }
+    let kinds = config
+        .items
+        .iter()
+        .map(|item| PlacementKind::Item(item.clone()))
+        .chain((0..config.scares).map(|_| PlacementKind::Scare));
+    for kind in kinds {
+        let room = if rooms.len() > 1 {
+            rng.gen_range(1..rooms.len())
+        } else {
+            0
+        };
+        // kept away from the walls, and the exit in the middle
+        let offset = Vec2::new(rng.gen_range(0.15..0.35), rng.gen_range(0.15..0.35))
+            * Vec2::new(
+                if rng.gen() { 1.0 } else { -1.0 },
+                if rng.gen() { 1.0 } else { -1.0 },
+            );
+        placements.push(Placement { room, offset, kind });
+    }
+
+    HouseLayout {
+        seed,
+        rooms,
+        placements,
+    }
+}
+
+/// Spawn the pieces, colliders and placements of a house, with the first room centred on `origin`
+pub fn spawn_house(
+    commands: &mut Commands,
+    layout: &HouseLayout,
+    config: &LayoutConfig,
+    origin: Vec3,
+    pieces: Option<&Gltf>,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+) {
+    let size = config.room_size;
+    let height = config.wall_height;
+    let fallback = materials.add(StandardMaterial {
+        base_color: Color::srgb(0.35, 0.33, 0.3),
+        ..default()
+    });
+    let mut piece = |commands: &mut Commands, name: &str, transform: Transform, extents: Vec3| {
+        let mut entity = commands.spawn((
+            RigidBody::Fixed,
+            Collider::cuboid(extents.x / 2.0, extents.y / 2.0, extents.z / 2.0),
+            Name::new(name.to_string()),
+            LevelScoped,
+            GameSession,
+        ));
+        match pieces.and_then(|gltf| gltf.named_scenes.get(name)) {
+            Some(scene) => entity.insert(SceneBundle {
+                scene: scene.clone(),
+                transform,
+                ..Default::default()
+            }),
+            None => entity.insert(PbrBundle {
+                mesh: meshes.add(Mesh::from(Cuboid::from_size(extents))),
+                material: fallback.clone(),
+                transform,
+                ..Default::default()
+            }),
+        };
+    };
+
+    let centre =
+        |room: &Room| origin + Vec3::new(room.cell.x as f32, 0.0, room.cell.y as f32) * size;
+
+    for room in &layout.rooms {
+        let centre = centre(room);
+        piece(
+            commands,
+            "floor",
+            Transform::from_translation(centre - Vec3::Y * WALL_THICKNESS / 2.0),
+            Vec3::new(size, WALL_THICKNESS, size),
+        );
+
+        for (side, offset) in SIDES.iter().enumerate() {
+            // walls between two rooms are spawned once, by the room west or north of them
+            let neighbour = layout
+                .rooms
+                .iter()
+                .any(|other| other.cell == room.neighbour(side));
+            if neighbour && matches!(side, 0 | 3) {
+                continue;
+            }
+
+            let rotation = Quat::from_rotation_y(-(side as f32) * std::f32::consts::FRAC_PI_2);
+            let wall = centre
+                + Vec3::new(offset.x as f32, 0.0, offset.y as f32) * size / 2.0
+                + Vec3::Y * height / 2.0;
+            if room.doors[side] {
+                // doorway pieces are modelled around the gap, the colliders are either side of it
+                let width = (size - DOOR_WIDTH) / 2.0;
+                for along in [-1.0, 1.0] {
+                    let position = wall + rotation * Vec3::X * along * (DOOR_WIDTH + width) / 2.0;
+                    commands.spawn((
+                        RigidBody::Fixed,
+                        Collider::cuboid(width / 2.0, height / 2.0, WALL_THICKNESS / 2.0),
+                        TransformBundle::from(
+                            Transform::from_translation(position).with_rotation(rotation),
+                        ),
+                        Name::new("doorway collider"),
+                        LevelScoped,
+                        GameSession,
+                    ));
+                }
+                if let Some(scene) = pieces.and_then(|gltf| gltf.named_scenes.get("doorway")) {
+                    commands.spawn((
+                        SceneBundle {
+                            scene: scene.clone(),
+                            transform: Transform::from_translation(wall).with_rotation(rotation),
+                            ..Default::default()
+                        },
+                        Name::new("doorway"),
+                        LevelScoped,
+                        GameSession,
+                    ));
+                }
+            } else {
+                piece(
+                    commands,
+                    "wall",
+                    Transform::from_translation(wall).with_rotation(rotation),
+                    Vec3::new(size, height, WALL_THICKNESS),
+                );
+            }
+        }
+    }
+
+    for placement in &layout.placements {
+        let position = centre(&layout.rooms[placement.room])
+            + Vec3::new(placement.offset.x, 0.0, placement.offset.y) * size;
+        let transform = Transform::from_translation(position + Vec3::Y);
+        match &placement.kind {
+            PlacementKind::Exit(to) => {
+                commands.spawn((
+                    Collider::cuboid(0.5, 1.0, 0.5),
+                    Sensor,
+                    ActiveEvents::COLLISION_EVENTS,
+                    TransformBundle::from(transform),
+                    LevelExit { to: to.clone() },
+                    Name::new(format!("exit to {to}")),
+                    LevelScoped,
+                    GameSession,
+                ));
+            }
+            PlacementKind::Scare => {
+                commands.spawn((
+                    Collider::cuboid(1.0, 1.0, 1.0),
+                    Sensor,
+                    ActiveEvents::COLLISION_EVENTS,
+                    TransformBundle::from(transform),
+                    ScareTrigger,
+                    ObjectiveTrigger("scare".into()),
+                    Name::new("scare"),
+                    LevelScoped,
+                    GameSession,
+                ));
+            }
+            PlacementKind::Item(item) => {
+                let mut entity = commands.spawn((
+                    HouseItem(item.clone()),
+                    Name::new(item.clone()),
+                    LevelScoped,
+                    GameSession,
+                ));
+                match pieces.and_then(|gltf| gltf.named_scenes.get(item.as_str())) {
+                    Some(scene) => entity.insert(SceneBundle {
+                        scene: scene.clone(),
+                        transform,
+                        ..Default::default()
+                    }),
+                    None => entity.insert(PbrBundle {
+                        mesh: meshes.add(Mesh::from(Cuboid::from_length(0.3))),
+                        material: materials.add(StandardMaterial {
+                            base_color: Color::srgb(0.8, 0.7, 0.2),
+                            ..default()
+                        }),
+                        transform,
+                        ..Default::default()
+                    }),
+                };
+            }
+        }
+    }
+}
@@ -0,0 +1,738 @@
+use crate::assets::{fallback_skybox, CollectionProgress, LevelAssets, LoadingProgress};
+use crate::rng::GameRng;
+use crate::skybox::SkyboxFlash;
+use crate::GameState;
+
+use super::capture::Evidence;
+use super::house::{generate, spawn_house, CurrentHouse, HouseSeed, LayoutConfig};
+use super::objectives::ObjectiveConfig;
+use super::{GameSession, Player, PlayerCamera};
+use bevy::asset::{LoadState, UntypedAssetId};
+use bevy::core_pipeline::Skybox;
+use bevy::gltf::Gltf;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use rand::Rng;
+use serde::Deserialize;
+
+const SKYBOX_BRIGHTNESS: f32 = 100.0;
+const FADE_OUT_SECS: f32 = 0.4;
+const FADE_IN_SECS: f32 = 0.6;
+/// Name level assets that failed to load are listed under in [`LoadingProgress`]
+pub const LEVEL_COLLECTION: &str = "Level";
+
+/// Streams levels listed in the [`LevelRegistry`] in and out while in `GameState::Game`
+#[derive(Default)]
+pub struct LevelPlugin {
+    /// Level to start in, see [`StartLevel`]
+    pub level: Option<String>,
+    /// Overrides where the player spawns in the start level
+    pub spawn: Option<Vec3>,
+}
+
+impl Plugin for LevelPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ChangeLevel>()
+            .insert_resource(StartLevel(self.level.clone()))
+            .insert_resource(StartSpawn(self.spawn))
+            .init_resource::<HouseSeed>()
+            .add_systems(OnEnter(GameState::Game), setup)
+            .add_systems(
+                Update,
+                (
+                    use_exits,
+                    change_level,
+                    fade,
+                    spawn_level.run_if(level_loaded),
+                    reload_environment,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Game)),
+            )
+            .add_systems(OnExit(GameState::Game), teardown);
+    }
+}
+
+/// Every level in the game, loaded from a `*.levels.ron` file
+#[derive(Asset, TypePath, Deserialize, Debug, Default)]
+pub struct LevelRegistry {
+    pub levels: Vec<Level>,
+    /// Goals across every level, see [`super::objectives::Objectives`]
+    #[serde(default)]
+    pub objectives: Vec<ObjectiveConfig>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Level {
+    pub name: String,
+    /// glTF file whose first scene makes up the level
+    #[serde(default)]
+    pub world: Option<String>,
+    /// Rooms generated around the spawn point, alongside or instead of the world
+    #[serde(default)]
+    pub layout: Option<LayoutConfig>,
+    /// Image loaded by `SkyboxLoader`
+    #[serde(default)]
+    pub skybox: Option<String>,
+    /// Looped for as long as the level is loaded
+    #[serde(default)]
+    pub music: Option<String>,
+    pub spawn: (f32, f32, f32),
+    pub ambient: AmbientConfig,
+    pub fog: FogConfig,
+    pub moon: MoonConfig,
+    /// Flat floor whose top sits at `y = 0`
+    #[serde(default)]
+    pub ground: Option<GroundConfig>,
+    /// Invisible colliders keeping the player inside the level
+    #[serde(default)]
+    pub walls: Vec<WallConfig>,
+    #[serde(default)]
+    pub exits: Vec<ExitConfig>,
+    /// Things worth catching on tape, see [`Evidence`]
+    #[serde(default)]
+    pub evidence: Vec<EvidenceConfig>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct AmbientConfig {
+    pub color: (u8, u8, u8),
+    pub brightness: f32,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct FogConfig {
+    pub color: (f32, f32, f32),
+    pub density: f32,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct MoonConfig {
+    pub illuminance: f32,
+    /// Angle below the horizon, in degrees
+    pub elevation: f32,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct GroundConfig {
+    pub size: (f32, f32, f32),
+    pub color: (f32, f32, f32),
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct WallConfig {
+    pub position: (f32, f32, f32),
+    pub size: (f32, f32, f32),
+}
+
+/// Trigger volume taking the player to another level
+#[derive(Deserialize, Debug, Clone)]
+pub struct ExitConfig {
+    pub to: String,
+    pub position: (f32, f32, f32),
+    pub size: (f32, f32, f32),
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct EvidenceConfig {
+    pub name: String,
+    pub position: (f32, f32, f32),
+}
+
+impl LevelRegistry {
+    pub fn get(&self, name: &str) -> Option<&Level> {
+        self.levels.iter().find(|level| level.name == name)
+    }
+
+    /// A single empty level, used when the registry itself failed to load
+    pub fn fallback() -> Self {
+        Self {
+            levels: vec![Level {
+                name: "fallback".into(),
+                world: None,
+                layout: None,
+                skybox: None,
+                music: None,
+                spawn: (0.0, 0.0, 0.0),
+                ambient: AmbientConfig {
+                    color: (255, 255, 255),
+                    brightness: 1.0,
+                },
+                fog: FogConfig {
+                    color: (0.05, 0.05, 0.05),
+                    density: 0.15,
+                },
+                moon: MoonConfig {
+                    illuminance: 320.0,
+                    elevation: 45.0,
+                },
+                ground: None,
+                walls: Vec::new(),
+                exits: Vec::new(),
+                evidence: Vec::new(),
+            }],
+            objectives: Vec::new(),
+        }
+    }
+}
+
+/// Level to start in when entering the game, the first in the registry when `None`
+#[derive(Resource, Default)]
+pub struct StartLevel(pub Option<String>);
+
+/// Spawn point used instead of the start level's own when entering the game
+#[derive(Resource, Default)]
+pub struct StartSpawn(pub Option<Vec3>);
+
+#[derive(Resource)]
+pub struct CurrentLevel(pub String);
+
+/// Request to fade out of the current level and stream in another
+#[derive(Event)]
+pub struct ChangeLevel(pub String);
+
+/// Tag component for everything belonging to the current level
+#[derive(Component)]
+pub struct LevelScoped;
+
+/// Tag component for the moon, ground and walls, respawned when the registry is reloaded
+#[derive(Component)]
+struct LevelEnvironment;
+
+#[derive(Component)]
+pub struct LevelExit {
+    pub to: String,
+}
+
+/// Full screen cover hiding the switch between levels
+#[derive(Component)]
+pub struct LevelFade;
+
+/// Switch to another level in progress, removed once it has faded in
+#[derive(Resource)]
+pub struct LevelTransition {
+    level: Level,
+    phase: TransitionPhase,
+    fade: Timer,
+}
+
+impl LevelTransition {
+    pub fn phase(&self) -> &TransitionPhase {
+        &self.phase
+    }
+}
+
+pub enum TransitionPhase {
+    FadeOut,
+    /// Streaming in the assets of the next level behind the cover
+    Loading(LevelHandles),
+    FadeIn,
+}
+
+pub struct LevelHandles {
+    world: Option<Handle<Gltf>>,
+    pieces: Option<Handle<Gltf>>,
+    skybox: Option<Handle<Image>>,
+    music: Option<Handle<AudioSource>>,
+}
+
+impl LevelHandles {
+    fn ids(&self) -> impl Iterator<Item = UntypedAssetId> {
+        [
+            self.world.as_ref().map(|handle| handle.id().untyped()),
+            self.pieces.as_ref().map(|handle| handle.id().untyped()),
+            self.skybox.as_ref().map(|handle| handle.id().untyped()),
+            self.music.as_ref().map(|handle| handle.id().untyped()),
+        ]
+        .into_iter()
+        .flatten()
+    }
+}
+
+fn registry<'a>(
+    assets: &LevelAssets,
+    registries: &'a Assets<LevelRegistry>,
+) -> Option<&'a LevelRegistry> {
+    registries.get(&assets.registry)
+}
+
+fn setup(
+    mut commands: Commands,
+    start: Res<StartLevel>,
+    start_spawn: Res<StartSpawn>,
+    assets: Res<LevelAssets>,
+    registries: Res<Assets<LevelRegistry>>,
+) {
+    let Some(registry) = registry(&assets, &registries) else {
+        warn!("Level registry is missing");
+        return;
+    };
+    let level = match &start.0 {
+        Some(name) => registry.get(name).or_else(|| {
+            warn!("Level {name} is not in the registry, starting in the first level");
+            registry.levels.first()
+        }),
+        None => registry.levels.first(),
+    };
+    // without a level to fade into, the cover would never lift
+    let Some(level) = level else {
+        warn!("Level registry is empty");
+        return;
+    };
+
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..Default::default()
+            },
+            background_color: Color::BLACK.into(),
+            z_index: ZIndex::Global(i32::MAX),
+            ..Default::default()
+        },
+        LevelFade,
+        GameSession,
+    ));
+
+    // already covered by the fade, so skip straight past fading out
+    let mut fade = Timer::from_seconds(FADE_OUT_SECS, TimerMode::Once);
+    fade.tick(fade.duration());
+    let mut level = level.clone();
+    if let Some(spawn) = start_spawn.0 {
+        level.spawn = spawn.into();
+    }
+    commands.insert_resource(LevelTransition {
+        level,
+        phase: TransitionPhase::FadeOut,
+        fade,
+    });
+}
+
+fn teardown(mut commands: Commands) {
+    commands.remove_resource::<LevelTransition>();
+    commands.remove_resource::<CurrentLevel>();
+    commands.remove_resource::<CurrentHouse>();
+}
+
+fn use_exits(
+    mut collisions: EventReader<CollisionEvent>,
+    players: Query<(), With<Player>>,
+    exits: Query<&LevelExit>,
+    mut writer: EventWriter<ChangeLevel>,
+) {
+    for collision in collisions.read() {
+        let CollisionEvent::Started(a, b, _) = collision else {
+            continue;
+        };
+        for (player, exit) in [(a, b), (b, a)] {
+            if let (true, Ok(exit)) = (players.contains(*player), exits.get(*exit)) {
+                writer.send(ChangeLevel(exit.to.clone()));
+            }
+        }
+    }
+}
+
+fn change_level(
+    mut commands: Commands,
+    mut events: EventReader<ChangeLevel>,
+    transition: Option<Res<LevelTransition>>,
+    assets: Res<LevelAssets>,
+    registries: Res<Assets<LevelRegistry>>,
+) {
+    let Some(ChangeLevel(name)) = events.read().last() else {
+        return;
+    };
+    if transition.is_some() {
+        return;
+    }
+    let Some(level) = registry(&assets, &registries).and_then(|registry| registry.get(name)) else {
+        warn!("Level {name} is not in the registry");
+        return;
+    };
+
+    commands.insert_resource(LevelTransition {
+        level: level.clone(),
+        phase: TransitionPhase::FadeOut,
+        fade: Timer::from_seconds(FADE_OUT_SECS, TimerMode::Once),
+    });
+}
+
+fn fade(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut rng: ResMut<GameRng>,
+    asset_server: Res<AssetServer>,
+    transition: Option<ResMut<LevelTransition>>,
+    mut covers: Query<&mut BackgroundColor, With<LevelFade>>,
+    level_entities: Query<Entity, With<LevelScoped>>,
+) {
+    let Some(mut transition) = transition else {
+        return;
+    };
+    transition.fade.tick(time.delta());
+
+    let color = match transition.phase {
+        TransitionPhase::FadeOut => {
+            if transition.fade.finished() {
+                for entity in &level_entities {
+                    commands.entity(entity).despawn_recursive();
+                }
+                let level = &transition.level;
+                transition.phase = TransitionPhase::Loading(LevelHandles {
+                    world: level.world.as_ref().map(|path| asset_server.load(path)),
+                    pieces: level
+                        .layout
+                        .as_ref()
+                        .and_then(|layout| layout.pieces.as_ref())
+                        .map(|path| asset_server.load(path)),
+                    skybox: level.skybox.as_ref().map(|path| asset_server.load(path)),
+                    music: level.music.as_ref().map(|path| asset_server.load(path)),
+                });
+            }
+            Color::BLACK.with_alpha(transition.fade.fraction())
+        }
+        // flicker like a tape being fast forwarded while the next level streams in
+        TransitionPhase::Loading(_) => {
+            Color::srgb_from_array([rng.stream("fade").gen_range(0.0..0.08); 3])
+        }
+        TransitionPhase::FadeIn => {
+            if transition.fade.finished() {
+                commands.remove_resource::<LevelTransition>();
+            }
+            Color::BLACK.with_alpha(transition.fade.fraction_remaining())
+        }
+    };
+
+    for mut background in covers.iter_mut() {
+        background.0 = color;
+    }
+}
+
+fn level_loaded(asset_server: Res<AssetServer>, transition: Option<Res<LevelTransition>>) -> bool {
+    let Some(TransitionPhase::Loading(handles)) = transition.as_ref().map(|t| &t.phase) else {
+        return false;
+    };
+    handles.ids().all(|id| {
+        matches!(
+            asset_server.get_load_state(id),
+            Some(LoadState::Loaded | LoadState::Failed(_))
+        )
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+fn spawn_level(
+    mut commands: Commands,
+    mut transition: ResMut<LevelTransition>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+    gltfs: Res<Assets<Gltf>>,
+    mut players: Query<&mut Transform, With<Player>>,
+    cameras: Query<Entity, With<PlayerCamera>>,
+    seed: Res<HouseSeed>,
+    mut rng: ResMut<GameRng>,
+    asset_server: Res<AssetServer>,
+    mut progress: ResMut<LoadingProgress>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let TransitionPhase::Loading(handles) = &transition.phase else {
+        return;
+    };
+    let level = &transition.level;
+
+    // placeholders stand in for whatever is missing, only useful while developing
+    let failures: Vec<_> = handles
+        .ids()
+        .filter_map(|id| match asset_server.get_load_state(id) {
+            Some(LoadState::Failed(error)) => {
+                let path = asset_server
+                    .get_path(id)
+                    .map_or_else(|| level.name.clone(), |path| path.to_string());
+                Some((path, error.to_string()))
+            }
+            _ => None,
+        })
+        .collect();
+    if !failures.is_empty() && !cfg!(debug_assertions) {
+        for (path, reason) in &failures {
+            error!("Failed to load {path}: {reason}");
+        }
+        let total = handles.ids().count();
+        progress
+            .collections
+            .retain(|collection| collection.name != LEVEL_COLLECTION);
+        progress.collections.push(CollectionProgress {
+            name: LEVEL_COLLECTION,
+            done: total - failures.len(),
+            total,
+            failures,
+        });
+        next_state.set(GameState::LoadFailed);
+        return;
+    }
+
+    match handles.world.as_ref().and_then(|handle| gltfs.get(handle)) {
+        Some(gltf) => {
+            commands.spawn((
+                SceneBundle {
+                    scene: gltf.scenes[0].clone(),
+                    transform: Transform::from_xyz(0.0, 0.0, 0.0).looking_at(Vec3::Z, Vec3::Y),
+                    ..Default::default()
+                },
+                AsyncSceneCollider {
+                    shape: Some(ComputedColliderShape::TriMesh),
+                    ..Default::default()
+                },
+                Name::new(level.name.clone()),
+                LevelScoped,
+                GameSession,
+            ));
+        }
+        None if level.world.is_some() => {
+            warn!(
+                "World of level {} is missing, spawning placeholder",
+                level.name
+            );
+            commands.spawn((
+                RigidBody::Fixed,
+                Collider::cuboid(1.0, 1.0, 1.0),
+                PbrBundle {
+                    mesh: meshes.add(Mesh::from(Cuboid::new(2.0, 2.0, 2.0))),
+                    material: materials.add(StandardMaterial {
+                        base_color: Color::srgb(1.0, 0.0, 1.0),
+                        unlit: true,
+                        ..default()
+                    }),
+                    transform: Transform::from_xyz(0.0, 1.0, 0.0),
+                    ..Default::default()
+                },
+                Name::new("missing world"),
+                LevelScoped,
+                GameSession,
+            ));
+        }
+        None => {}
+    }
+
+    match &level.layout {
+        Some(config) => {
+            let seed = seed.0.unwrap_or_else(|| rng.stream("house").gen());
+            info!("Generating {} from seed {seed}", level.name);
+            spawn_house(
+                &mut commands,
+                &generate(seed, config),
+                config,
+                level.spawn.into(),
+                handles.pieces.as_ref().and_then(|handle| gltfs.get(handle)),
+                &mut meshes,
+                &mut materials,
+            );
+            commands.insert_resource(CurrentHouse(seed));
+        }
+        None => commands.remove_resource::<CurrentHouse>(),
+    }
+
+    if let Some(music) = &handles.music {
+        commands.spawn((
+            AudioBundle {
+                source: music.clone(),
+                settings: PlaybackSettings::LOOP,
+            },
+            Name::new("music"),
+            LevelScoped,
+            GameSession,
+        ));
+    }
+
+    for exit in &level.exits {
+        let (x, y, z) = exit.size;
+        commands.spawn((
+            Collider::cuboid(x / 2.0, y / 2.0, z / 2.0),
+            Sensor,
+            ActiveEvents::COLLISION_EVENTS,
+            TransformBundle::from(Transform::from_translation(exit.position.into())),
+            LevelExit {
+                to: exit.to.clone(),
+            },
+            Name::new(format!("exit to {}", exit.to)),
+            LevelScoped,
+            GameSession,
+        ));
+    }
+
+    for evidence in &level.evidence {
+        commands.spawn((
+            PbrBundle {
+                mesh: meshes.add(Mesh::from(Cuboid::from_length(0.4))),
+                material: materials.add(StandardMaterial {
+                    base_color: Color::srgb(0.35, 0.05, 0.05),
+                    ..default()
+                }),
+                transform: Transform::from_translation(evidence.position.into()),
+                ..Default::default()
+            },
+            // something for captures to check the view against
+            Collider::cuboid(0.2, 0.2, 0.2),
+            Evidence(evidence.name.clone()),
+            Name::new(evidence.name.clone()),
+            LevelScoped,
+            GameSession,
+        ));
+    }
+
+    // cubemaps are converted as they load by `SkyboxLoader`, anything else can't be rendered
+    let skybox = match handles
+        .skybox
+        .as_ref()
+        .map(|handle| (handle, images.get(handle)))
+    {
+        Some((handle, Some(image))) if image.texture_descriptor.array_layer_count() == 6 => {
+            handle.clone()
+        }
+        Some((_, Some(image))) => {
+            warn!(
+                "Skybox is a {}x{} image rather than a cubemap, name it `*.sky.png` so it is converted, using fallback",
+                image.width(),
+                image.height()
+            );
+            images.add(fallback_skybox())
+        }
+        Some((_, None)) => {
+            warn!("Skybox of level {} is missing, using fallback", level.name);
+            images.add(fallback_skybox())
+        }
+        None => images.add(fallback_skybox()),
+    };
+
+    for camera in &cameras {
+        commands.entity(camera).insert((
+            Skybox {
+                image: skybox.clone(),
+                brightness: SKYBOX_BRIGHTNESS,
+            },
+            SkyboxFlash::new(SKYBOX_BRIGHTNESS, SKYBOX_BRIGHTNESS * 40.0, 0.4),
+        ));
+    }
+    spawn_environment(&mut commands, level, &mut meshes, &mut materials, &cameras);
+
+    for mut transform in players.iter_mut() {
+        transform.translation = level.spawn.into();
+    }
+
+    commands.insert_resource(CurrentLevel(level.name.clone()));
+    transition.phase = TransitionPhase::FadeIn;
+    transition.fade = Timer::from_seconds(FADE_IN_SECS, TimerMode::Once);
+}
+
+// Everything tuned in the registry rather than modelled in the world, cheap enough to respawn
+// whenever the registry changes on disk
+fn spawn_environment(
+    commands: &mut Commands,
+    level: &Level,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+    cameras: &Query<Entity, With<PlayerCamera>>,
+) {
+    for camera in cameras {
+        commands.entity(camera).insert(FogSettings {
+            color: Color::srgb_from_array(level.fog.color.into()),
+            falloff: FogFalloff::Exponential {
+                density: level.fog.density,
+            },
+            ..Default::default()
+        });
+    }
+
+    let (r, g, b) = level.ambient.color;
+    commands.insert_resource(AmbientLight {
+        color: Color::srgb_u8(r, g, b),
+        brightness: level.ambient.brightness,
+    });
+
+    commands.spawn((
+        DirectionalLightBundle {
+            directional_light: DirectionalLight {
+                illuminance: level.moon.illuminance,
+                ..default()
+            },
+            transform: Transform::from_xyz(0.0, 2.0, 0.0)
+                .with_rotation(Quat::from_rotation_x(-level.moon.elevation.to_radians())),
+            ..default()
+        },
+        Name::new("moon"),
+        LevelEnvironment,
+        LevelScoped,
+        GameSession,
+    ));
+
+    if let Some(ground) = &level.ground {
+        let (x, y, z) = ground.size;
+        commands.spawn((
+            RigidBody::Fixed,
+            Collider::cuboid(x / 2.0, y / 2.0, z / 2.0),
+            PbrBundle {
+                mesh: meshes.add(Mesh::from(Cuboid::new(x, y, z))),
+                material: materials.add(StandardMaterial {
+                    base_color: Color::srgb_from_array(ground.color.into()),
+                    unlit: true,
+                    ..default()
+                }),
+                transform: Transform::from_xyz(0.0, -y / 2.0, 0.0),
+                ..Default::default()
+            },
+            Name::new("ground"),
+            LevelEnvironment,
+            LevelScoped,
+            GameSession,
+        ));
+    }
+
+    for wall in &level.walls {
+        let (x, y, z) = wall.size;
+        commands.spawn((
+            RigidBody::Fixed,
+            Collider::cuboid(x / 2.0, y / 2.0, z / 2.0),
+            TransformBundle::from(Transform::from_translation(wall.position.into())),
+            Name::new("wall"),
+            LevelEnvironment,
+            LevelScoped,
+            GameSession,
+        ));
+    }
+}
+
+// Lets fog, lighting and bounds be tuned while playing when assets are watched for changes,
+// worlds, skyboxes and music only change on the next visit
+#[allow(clippy::too_many_arguments)]
+fn reload_environment(
+    mut commands: Commands,
+    mut events: EventReader<AssetEvent<LevelRegistry>>,
+    assets: Res<LevelAssets>,
+    registries: Res<Assets<LevelRegistry>>,
+    current: Option<Res<CurrentLevel>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    cameras: Query<Entity, With<PlayerCamera>>,
+    environment: Query<Entity, With<LevelEnvironment>>,
+) {
+    let modified = events
+        .read()
+        .any(|event| event.is_modified(&assets.registry));
+    let Some(current) = current.filter(|_| modified) else {
+        return;
+    };
+    let Some(level) = registry(&assets, &registries).and_then(|registry| registry.get(&current.0))
+    else {
+        warn!("Level {} is no longer in the registry", current.0);
+        return;
+    };
+
+    info!("Reloading environment of level {}", level.name);
+    for entity in &environment {
+        commands.entity(entity).despawn_recursive();
+    }
+    spawn_environment(&mut commands, level, &mut meshes, &mut materials, &cameras);
+}
@@ -0,0 +1,228 @@
+use crate::assets::LevelAssets;
+use crate::GameState;
+
+use super::capture::EvidenceCaptured;
+use super::g3d::pick_up;
+use super::level::{CurrentLevel, LevelExit, LevelRegistry};
+use super::Player;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use serde::Deserialize;
+
+/// Tracks the [`Objectives`] listed in the level registry as the player plays
+pub struct ObjectivesPlugin;
+
+impl Plugin for ObjectivesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ObjectiveEvent>()
+            .add_event::<ObjectiveChanged>()
+            // sent by `CapturePlugin`, evidence objectives never complete without it
+            .add_event::<EvidenceCaptured>()
+            .init_resource::<Objectives>()
+            .add_systems(OnEnter(GameState::Game), setup)
+            .add_systems(
+                Update,
+                // items picked up this frame count this frame
+                (collect_events.after(pick_up), track)
+                    .chain()
+                    .run_if(in_state(GameState::Game)),
+            )
+            .add_systems(OnExit(GameState::Game), |mut commands: Commands| {
+                commands.insert_resource(Objectives::default());
+            });
+    }
+}
+
+/// Something that happened in the game that objectives can be completed or failed by
+#[derive(Event, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum ObjectiveEvent {
+    /// A house item, by name, was picked up
+    ItemPickedUp(String),
+    /// The player walked into an [`ObjectiveTrigger`], by name
+    TriggerEntered(String),
+    /// The player reached a level exit, by the level it leads to
+    ExitReached(String),
+    /// A level, by name, finished loading around the player
+    LevelEntered(String),
+    /// Evidence, by name, was caught on tape for the first time
+    EvidenceCaptured(String),
+}
+
+/// Sensor sending [`ObjectiveEvent::TriggerEntered`] when the player walks into it
+#[derive(Component, Debug, Clone)]
+pub struct ObjectiveTrigger(pub String);
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ObjectiveConfig {
+    /// Its text is looked up as `objective-<id>`
+    pub id: String,
+    pub complete: ObjectiveEvent,
+    #[serde(default)]
+    pub fail: Option<ObjectiveEvent>,
+    /// Objectives to complete before this one shows up
+    #[serde(default)]
+    pub after: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectiveState {
+    /// Waiting on the objectives it comes after
+    Hidden,
+    Active,
+    Completed,
+    Failed,
+}
+
+#[derive(Debug, Clone)]
+pub struct Objective {
+    pub config: ObjectiveConfig,
+    pub state: ObjectiveState,
+}
+
+/// Sent whenever an objective shows up, is completed or is failed
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct ObjectiveChanged {
+    pub id: String,
+    pub state: ObjectiveState,
+}
+
+/// Every objective of the session, in the order they are listed
+#[derive(Resource, Debug, Default)]
+pub struct Objectives(pub Vec<Objective>);
+
+impl Objectives {
+    /// Start every objective hidden, then show the ones that don't come after any other
+    pub fn new(configs: &[ObjectiveConfig]) -> (Self, Vec<ObjectiveChanged>) {
+        let mut objectives = Self(
+            configs
+                .iter()
+                .map(|config| Objective {
+                    config: config.clone(),
+                    state: ObjectiveState::Hidden,
+                })
+                .collect(),
+        );
+        let mut changes = Vec::new();
+        objectives.reveal(&mut changes);
+        (objectives, changes)
+    }
+
+    /// Complete or fail whatever active objectives `event` is for, returning every change
+    pub fn handle(&mut self, event: &ObjectiveEvent) -> Vec<ObjectiveChanged> {
+        let mut changes = Vec::new();
+        for objective in &mut self.0 {
+            if objective.state != ObjectiveState::Active {
+                continue;
+            }
+            let state = if objective.config.complete == *event {
+                ObjectiveState::Completed
+            } else if objective.config.fail.as_ref() == Some(event) {
+                ObjectiveState::Failed
+            } else {
+                continue;
+            };
+            objective.state = state;
+            changes.push(ObjectiveChanged {
+                id: objective.config.id.clone(),
+                state,
+            });
+        }
+        if !changes.is_empty() {
+            self.reveal(&mut changes);
+        }
+        changes
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Objective> {
+        self.0.iter().find(|objective| objective.config.id == id)
+    }
+
+    fn reveal(&mut self, changes: &mut Vec<ObjectiveChanged>) {
+        let completed: Vec<String> = self
+            .0
+            .iter()
+            .filter(|objective| objective.state == ObjectiveState::Completed)
+            .map(|objective| objective.config.id.clone())
+            .collect();
+        for objective in &mut self.0 {
+            if objective.state == ObjectiveState::Hidden
+                && objective
+                    .config
+                    .after
+                    .iter()
+                    .all(|id| completed.contains(id))
+            {
+                objective.state = ObjectiveState::Active;
+                changes.push(ObjectiveChanged {
+                    id: objective.config.id.clone(),
+                    state: ObjectiveState::Active,
+                });
+            }
+        }
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    assets: Res<LevelAssets>,
+    registries: Res<Assets<LevelRegistry>>,
+    mut changed: EventWriter<ObjectiveChanged>,
+) {
+    let configs = registries
+        .get(&assets.registry)
+        .map(|registry| registry.objectives.as_slice())
+        .unwrap_or_default();
+    let (objectives, changes) = Objectives::new(configs);
+    commands.insert_resource(objectives);
+    changed.send_batch(changes);
+}
+
+// Turn whatever else happened this frame into objective events
+fn collect_events(
+    mut collisions: EventReader<CollisionEvent>,
+    mut evidence: EventReader<EvidenceCaptured>,
+    level: Option<Res<CurrentLevel>>,
+    players: Query<(), With<Player>>,
+    triggers: Query<&ObjectiveTrigger>,
+    exits: Query<&LevelExit>,
+    mut writer: EventWriter<ObjectiveEvent>,
+) {
+    for collision in collisions.read() {
+        let CollisionEvent::Started(a, b, _) = collision else {
+            continue;
+        };
+        for (player, other) in [(a, b), (b, a)] {
+            if !players.contains(*player) {
+                continue;
+            }
+            if let Ok(trigger) = triggers.get(*other) {
+                writer.send(ObjectiveEvent::TriggerEntered(trigger.0.clone()));
+            }
+            if let Ok(exit) = exits.get(*other) {
+                writer.send(ObjectiveEvent::ExitReached(exit.to.clone()));
+            }
+        }
+    }
+
+    for EvidenceCaptured(name) in evidence.read() {
+        writer.send(ObjectiveEvent::EvidenceCaptured(name.clone()));
+    }
+
+    if let Some(level) = level.filter(|level| level.is_changed()) {
+        writer.send(ObjectiveEvent::LevelEntered(level.0.clone()));
+    }
+}
+
+fn track(
+    mut objectives: ResMut<Objectives>,
+    mut events: EventReader<ObjectiveEvent>,
+    mut changed: EventWriter<ObjectiveChanged>,
+) {
+    for event in events.read() {
+        let changes = objectives.handle(event);
+        for change in &changes {
+            info!("Objective {} is now {:?}", change.id, change.state);
+        }
+        changed.send_batch(changes);
+    }
+}
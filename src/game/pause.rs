@@ -1,57 +1,140 @@
-use crate::{GameState, GAME_NAME};
+use crate::accessibility::{settings_ui, Accessibility};
+use crate::localization::{Locale, Localization};
+use crate::osd::{self, Backdrop, Hiss, OsdMenu};
+use crate::GameState;
 
-use super::{despawn_screen, GameplayState};
+use super::capture::{gallery_ui, Gallery};
+use super::objectives::{ObjectiveState, Objectives};
+use super::{despawn_screen, pause_pressed, Action, GameplayState};
 use bevy::prelude::*;
-use bevy_egui::{egui, EguiContexts, EguiPlugin};
+use leafwing_input_manager::prelude::*;
 
 pub struct PausePlugin;
 
 impl Plugin for PausePlugin {
     fn build(&self, app: &mut App) {
-        if !app.is_plugin_added::<EguiPlugin>() {
-            app.add_plugins(EguiPlugin);
-        }
-
-        app.add_systems(
-            Update,
-            ui.run_if(in_state(GameState::Game).and_then(in_state(GameplayState::Paused))),
-        )
-        .add_systems(
-            OnExit(GameplayState::Paused),
-            despawn_screen::<OnPauseScreen>,
-        );
+        app.add_systems(OnEnter(GameplayState::Paused), ambience)
+            .add_systems(
+                Update,
+                ui.run_if(in_state(GameState::Game).and_then(in_state(GameplayState::Paused))),
+            )
+            .add_systems(
+                OnExit(GameplayState::Paused),
+                despawn_screen::<OnPauseScreen>,
+            );
     }
 }
 
 #[derive(Component)]
 struct OnPauseScreen;
 
+// Gameplay audio is paused along with everything else, leaving only the tape hissing
+fn ambience(mut commands: Commands, mut hisses: ResMut<Assets<Hiss>>) {
+    commands.spawn((
+        AudioSourceBundle {
+            source: hisses.add(Hiss { volume: 0.05 }),
+            settings: PlaybackSettings::DESPAWN,
+        },
+        OnPauseScreen,
+    ));
+}
+
+// Sub-screens reached from the pause menu
+#[derive(Default, PartialEq, Eq)]
+enum PauseScreen {
+    #[default]
+    Pause,
+    Settings,
+    Gallery,
+}
+
+#[allow(clippy::too_many_arguments)]
 fn ui(
-    mut contexts: EguiContexts,
+    mut menu: OsdMenu,
+    mut accessibility: ResMut<Accessibility>,
+    localization: Res<Localization>,
+    mut locale: ResMut<Locale>,
+    gallery: Option<Res<Gallery>>,
+    objectives: Option<Res<Objectives>>,
+    mut screen: Local<PauseScreen>,
+    mut selected: Local<usize>,
+    pause: Query<&ActionState<Action>>,
     mut next_game_state: ResMut<NextState<GameState>>,
     mut next_gameplay_state: ResMut<NextState<GameplayState>>,
 ) {
-    let ctx = contexts.ctx_mut();
-
-    egui::CentralPanel::default().show(ctx, |ui| {
-        ui.with_layout(egui::Layout::top_down(egui::Align::Center), |ui| {
-            ui.style_mut().spacing.item_spacing = egui::vec2(0.0, 10.0);
-
-            ui.add(egui::Label::new(egui::RichText::new(GAME_NAME).size(64.0)));
+    match *screen {
+        PauseScreen::Pause => {}
+        PauseScreen::Settings => {
+            menu.show("settings", Backdrop::Frozen, |ui, focus| {
+                let mut edited = accessibility.clone();
+                if settings_ui(ui, focus, &localization, &mut locale, &mut edited) {
+                    *screen = PauseScreen::Pause;
+                }
+                accessibility.set_if_neq(edited);
+            });
+            return;
+        }
+        PauseScreen::Gallery => {
+            // only reachable with `CapturePlugin`, see the pause menu below
+            let Some(gallery) = gallery.as_deref() else {
+                *screen = PauseScreen::Pause;
+                return;
+            };
+            *selected = (*selected).min(gallery.captures.len().saturating_sub(1));
+            let texture = gallery
+                .captures
+                .get(*selected)
+                .and_then(|capture| capture.image.as_ref())
+                .map(|image| menu.image(image));
+            menu.show("gallery", Backdrop::Frozen, |ui, focus| {
+                if gallery_ui(ui, focus, &localization, gallery, &mut selected, texture) {
+                    *screen = PauseScreen::Pause;
+                }
+            });
+            return;
+        }
+    }
 
-            ui.add_space(10.0);
+    // the game's own OSD already reads "PAUSE", see `g2d`
+    menu.show("pause", Backdrop::Frozen, |ui, focus| {
+        let text = |key: &str| localization.text(key);
+        ui.add_space(ui.available_height() / 3.0);
 
-            let resume = ui.add(egui::Button::new(egui::RichText::new("Resume").size(32.0)));
-            let main_menu = ui.add(egui::Button::new(
-                egui::RichText::new("Main Menu").size(24.0),
-            ));
+        let resume = focus.button(ui, osd::text(text("pause-resume"), 32.0));
+        if gallery.is_some() && focus.button(ui, osd::text(text("pause-gallery"), 24.0)) {
+            *screen = PauseScreen::Gallery;
+        }
+        if focus.button(ui, osd::text(text("pause-setup"), 24.0)) {
+            *screen = PauseScreen::Settings;
+        }
+        let main_menu = focus.button(ui, osd::text(text("pause-main-menu"), 24.0));
 
-            if resume.clicked() {
-                next_gameplay_state.set(GameplayState::Playing);
-            }
-            if main_menu.clicked() {
-                next_game_state.set(GameState::Menu);
+        // objectives still waiting on others stay a surprise
+        let shown = objectives
+            .iter()
+            .flat_map(|objectives| objectives.0.iter())
+            .filter(|objective| objective.state != ObjectiveState::Hidden);
+        for (index, objective) in shown.enumerate() {
+            if index == 0 {
+                ui.add_space(20.0);
+                ui.label(osd::text(text("objectives"), 32.0));
             }
-        });
+            let name = text(&format!("objective-{}", objective.config.id));
+            let label = match objective.state {
+                ObjectiveState::Failed => {
+                    osd::text(format!("✖ {name}"), 24.0).color(accessibility.danger_egui())
+                }
+                ObjectiveState::Completed => osd::text(format!("✔ {name}"), 24.0),
+                _ => osd::text(format!("▶ {name}"), 24.0),
+            };
+            ui.label(label);
+        }
+
+        if resume || focus.back() || pause_pressed(&pause) {
+            next_gameplay_state.set(GameplayState::Playing);
+        }
+        if main_menu {
+            next_game_state.set(GameState::Menu);
+        }
     });
 }
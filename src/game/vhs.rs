@@ -1,17 +1,38 @@
 use bevy::{
     prelude::*,
-    render::render_resource::{AsBindGroup, ShaderRef},
+    render::render_resource::{
+        AsBindGroup, BlendComponent, BlendFactor, BlendOperation, BlendState,
+        RenderPipelineDescriptor, ShaderRef,
+    },
+    ui::UiMaterialKey,
 };
 
-use crate::{despawn_screen, GameState};
+use super::camcorder::Camcorder;
+use super::{GameSession, GameplayState, Player};
+use crate::accessibility::Accessibility;
+use crate::GameState;
 
 pub struct VHSPlugin;
 
 impl Plugin for VHSPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(MaterialPlugin::<VHSShader>::default())
-            .add_systems(OnEnter(GameState::Game), setup)
-            .add_systems(OnExit(GameState::Game), despawn_screen::<OnShader>);
+        app.add_plugins((
+            MaterialPlugin::<VHSShader>::default(),
+            UiMaterialPlugin::<TapeGrain>::default(),
+            UiMaterialPlugin::<NightVisionFilter>::default(),
+        ))
+        .init_resource::<VhsPreset>()
+        .add_systems(OnEnter(GameState::Game), setup)
+        .add_systems(
+            Update,
+            (update_grain, update_night_vision)
+                .run_if(in_state(GameState::Game).and_then(in_state(GameplayState::Playing))),
+        )
+        .add_systems(
+            Update,
+            apply_preset
+                .run_if(resource_changed::<VhsPreset>.or_else(resource_changed::<Accessibility>)),
+        );
     }
 }
 
@@ -23,6 +44,48 @@ pub struct VHSShader {
     #[texture(101, dimension = "2d")]
     #[sampler(102)]
     pub img: Handle<Image>,
+
+    /// Strength of the rolling noise, lowered by [`Accessibility::flashing`]
+    #[uniform(103)]
+    pub noise: f32,
+}
+
+/// How worn the tape looks, tints every [`VHSShader`] and the [`TapeGrain`] over the game
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum VhsPreset {
+    Clean,
+    #[default]
+    Worn,
+    Damaged,
+}
+
+impl VhsPreset {
+    pub const ALL: [Self; 3] = [Self::Clean, Self::Worn, Self::Damaged];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Clean => "clean",
+            Self::Worn => "worn",
+            Self::Damaged => "damaged",
+        }
+    }
+
+    pub fn tint(&self) -> LinearRgba {
+        match self {
+            Self::Clean => LinearRgba::WHITE,
+            Self::Worn => LinearRgba::rgb(0.95, 0.9, 0.85),
+            Self::Damaged => LinearRgba::rgb(0.8, 0.7, 0.75),
+        }
+    }
+
+    /// Multiplies how heavy the [`TapeGrain`] is
+    pub fn wear(&self) -> f32 {
+        match self {
+            Self::Clean => 0.5,
+            Self::Worn => 1.0,
+            Self::Damaged => 2.0,
+        }
+    }
 }
 
 impl Material for VHSShader {
@@ -31,10 +94,103 @@ impl Material for VHSShader {
     }
 }
 
-#[derive(Component)]
-struct OnShader;
+/// Tape noise drawn over the game, heavier the further the camcorder zooms in
+#[derive(Asset, AsBindGroup, TypePath, Debug, Clone, Default)]
+pub struct TapeGrain {
+    #[uniform(0)]
+    pub strength: f32,
+
+    #[uniform(1)]
+    pub time: f32,
+
+    /// Colour of the speckles, see [`VhsPreset::tint`]
+    #[uniform(2)]
+    pub tint: LinearRgba,
+}
+
+impl UiMaterial for TapeGrain {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/grain.wgsl".into()
+    }
+}
+
+/// Turns the view green for night vision by multiplying everything drawn below it by `tint`.
+///
+/// UI is drawn over the tonemapped image, so this only needs the camera to have dropped the
+/// colour first for the result to be monochrome green, see `camcorder::apply_view`.
+#[derive(Asset, AsBindGroup, TypePath, Debug, Clone, Default)]
+pub struct NightVisionFilter {
+    /// White leaves the view as it is
+    #[uniform(0)]
+    pub tint: LinearRgba,
+}
+
+impl UiMaterial for NightVisionFilter {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/night_vision.wgsl".into()
+    }
+
+    fn specialize(descriptor: &mut RenderPipelineDescriptor, _key: UiMaterialKey<Self>) {
+        let Some(fragment) = &mut descriptor.fragment else {
+            return;
+        };
+        for target in fragment.targets.iter_mut().flatten() {
+            target.blend = Some(BlendState {
+                color: BlendComponent {
+                    src_factor: BlendFactor::Dst,
+                    dst_factor: BlendFactor::Zero,
+                    operation: BlendOperation::Add,
+                },
+                alpha: BlendComponent {
+                    src_factor: BlendFactor::Zero,
+                    dst_factor: BlendFactor::One,
+                    operation: BlendOperation::Add,
+                },
+            });
+        }
+    }
+}
+
+/// Green of a night vision screen
+pub const NIGHT_VISION_TINT: LinearRgba = LinearRgba::rgb(0.2, 1.0, 0.3);
+
+fn setup(
+    mut commands: Commands,
+    mut grains: ResMut<Assets<TapeGrain>>,
+    mut filters: ResMut<Assets<NightVisionFilter>>,
+) {
+    commands.spawn((
+        MaterialNodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..Default::default()
+            },
+            material: filters.add(NightVisionFilter::default()),
+            // below the grain, which stays as bright as it would be
+            z_index: ZIndex::Global(-2),
+            ..Default::default()
+        },
+        GameSession,
+    ));
+
+    commands.spawn((
+        MaterialNodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..Default::default()
+            },
+            material: grains.add(TapeGrain::default()),
+            // below the viewfinder
+            z_index: ZIndex::Global(-1),
+            ..Default::default()
+        },
+        GameSession,
+    ));
 
-fn setup(mut commands: Commands) {
     // spawn 2D overlay
     commands.spawn((
         Camera2dBundle {
@@ -45,7 +201,64 @@ fn setup(mut commands: Commands) {
             },
             ..Default::default()
         },
-        OnShader,
+        GameSession,
     ));
     info!("Spawned Camera");
 }
+
+/// Tint every [`VHSShader`] with the current [`VhsPreset`]
+pub fn apply_preset(
+    preset: Res<VhsPreset>,
+    accessibility: Res<Accessibility>,
+    mut materials: ResMut<Assets<VHSShader>>,
+) {
+    for (_, material) in materials.iter_mut() {
+        material.color = preset.tint();
+        material.noise = accessibility.vhs_noise();
+    }
+}
+
+/// Digital zoom blows the tape's noise up along with the image, night vision adds gain and more
+pub fn update_grain(
+    time: Res<Time>,
+    preset: Res<VhsPreset>,
+    accessibility: Res<Accessibility>,
+    players: Query<(&Camcorder, &Player)>,
+    mut grains: ResMut<Assets<TapeGrain>>,
+) {
+    let strength = players.iter().next().map_or(0.05, |(camcorder, player)| {
+        let zoom = if camcorder.raised {
+            camcorder.zoom
+        } else {
+            1.0
+        };
+        let gain = if camcorder.night_vision_on(player) {
+            0.15
+        } else {
+            0.0
+        };
+        0.05 + (zoom - 1.0) * 0.04 + gain
+    });
+    for (_, grain) in grains.iter_mut() {
+        grain.strength = strength * preset.wear() * accessibility.vhs_noise();
+        grain.tint = preset.tint();
+        grain.time = time.elapsed_seconds_wrapped();
+    }
+}
+
+/// Tint the view green while the player looks through night vision
+pub fn update_night_vision(
+    players: Query<(&Camcorder, &Player)>,
+    mut filters: ResMut<Assets<NightVisionFilter>>,
+) {
+    let night_vision = players
+        .iter()
+        .any(|(camcorder, player)| camcorder.night_vision_on(player));
+    let tint = match night_vision {
+        true => NIGHT_VISION_TINT,
+        false => LinearRgba::WHITE,
+    };
+    for (_, filter) in filters.iter_mut() {
+        filter.tint = tint;
+    }
+}
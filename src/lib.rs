@@ -0,0 +1,178 @@
+pub mod accessibility;
+pub mod assets;
+pub mod cli;
+#[cfg(feature = "debug")]
+pub mod console;
+#[cfg(feature = "debug")]
+pub mod debug;
+pub mod error;
+pub mod game;
+pub mod localization;
+pub mod menu;
+pub mod navigation;
+pub mod osd;
+#[cfg(feature = "debug")]
+pub mod perf;
+pub mod rng;
+pub mod skybox;
+pub mod splash;
+
+use assets::{AssetPaths, AssetsPlugin};
+use bevy::{app::PluginGroupBuilder, prelude::*};
+use bevy_egui::EguiPlugin;
+use std::ops::RangeInclusive;
+
+pub const GAME_NAME: &str = "Jophus' Horror";
+
+// Enum that will be used as a global state for the game
+#[derive(Clone, Eq, PartialEq, Debug, Hash, Default, States)]
+pub enum GameState {
+    #[default]
+    Splash,
+    Menu,
+    Game,
+    // An asset collection failed to load
+    LoadFailed,
+}
+
+/// Every plugin making up the game.
+///
+/// Expects `DefaultPlugins` (or an equivalent set) to already be added.
+/// Individual plugins can be swapped out with `.set(...)` or removed with `.disable::<T>()`.
+pub struct HorrorPlugins {
+    pub assets: AssetPaths,
+    /// Include the main menu, otherwise loading continues straight into the game
+    pub menu: bool,
+    /// Include the splash screen shown while assets load
+    pub splash: bool,
+    /// Add `EguiPlugin`, disable when the host app already owns it
+    pub egui: bool,
+    /// Include the VHS post-processing, only available with the `shaders` feature
+    pub shaders: bool,
+    /// Seed for all gameplay randomness, see [`rng::RngPlugin`]
+    pub seed: Option<u64>,
+    /// Level to start in, the first in the registry when `None`
+    pub level: Option<String>,
+    /// Overrides where the player spawns in the start level
+    pub spawn: Option<Vec3>,
+    /// Language to start in, see [`localization::LocalizationPlugin`]
+    pub locale: Option<String>,
+}
+
+impl Default for HorrorPlugins {
+    fn default() -> Self {
+        Self {
+            assets: AssetPaths::default(),
+            menu: true,
+            splash: true,
+            egui: true,
+            shaders: true,
+            seed: None,
+            level: None,
+            spawn: None,
+            locale: None,
+        }
+    }
+}
+
+impl PluginGroup for HorrorPlugins {
+    fn build(self) -> PluginGroupBuilder {
+        let mut group = PluginGroupBuilder::start::<Self>();
+
+        if self.egui {
+            group = group.add(EguiPlugin);
+        }
+
+        group = group
+            .add(StatePlugin)
+            .add(rng::RngPlugin { seed: self.seed })
+            .add(skybox::SkyboxPlugin)
+            .add(AssetsPlugin {
+                paths: self.assets,
+                next_state: if self.menu {
+                    GameState::Menu
+                } else {
+                    GameState::Game
+                },
+            })
+            .add(navigation::NavigationPlugin)
+            .add(osd::OsdPlugin)
+            .add(accessibility::AccessibilityPlugin)
+            .add(localization::LocalizationPlugin {
+                locale: self.locale,
+                ..default()
+            })
+            .add(error::ErrorPlugin)
+            .add(game::GamePlugin)
+            .add(game::g2d::G2dPlugin)
+            .add(game::g3d::G3dPlugin)
+            .add(game::camcorder::CamcorderPlugin)
+            .add(game::capture::CapturePlugin)
+            .add(game::objectives::ObjectivesPlugin)
+            .add(game::level::LevelPlugin {
+                level: self.level,
+                spawn: self.spawn,
+            })
+            .add(game::pause::PausePlugin);
+
+        #[cfg(feature = "shaders")]
+        if self.shaders {
+            group = group.add(game::vhs::VHSPlugin);
+        }
+
+        if self.splash {
+            group = group.add(splash::SplashPlugin);
+        }
+        if self.menu {
+            group = group.add(menu::MenuPlugin);
+        }
+
+        #[cfg(feature = "debug")]
+        {
+            group = group
+                .add(debug::DebugPlugin)
+                .add(console::ConsolePlugin)
+                .add(perf::PerfPlugin)
+                .add(game::debug3d::Debug3DPlugin);
+        }
+
+        group
+    }
+}
+
+/// Declares the global `GameState`
+pub struct StatePlugin;
+
+impl Plugin for StatePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_state::<GameState>();
+    }
+}
+
+/// Generic system that warns whenever the number of entities with component `T` leaves `expected`,
+/// only reporting again once the count changes
+pub fn warn_cardinality<T: Component>(
+    expected: RangeInclusive<usize>,
+) -> impl FnMut(Query<(), With<T>>, Local<Option<usize>>) {
+    move |query, mut reported| {
+        let count = query.iter().count();
+        if expected.contains(&count) {
+            *reported = None;
+        } else if *reported != Some(count) {
+            warn!(
+                "expected {:?} entities with {}, found {}",
+                expected,
+                std::any::type_name::<T>(),
+                count
+            );
+            *reported = Some(count);
+        }
+    }
+}
+
+// Generic system that takes a component as a parameter, and will despawn all entities with that component
+pub fn despawn_screen<T: Component>(to_despawn: Query<Entity, With<T>>, mut commands: Commands) {
+    for entity in &to_despawn {
+        commands.entity(entity).despawn_recursive();
+    }
+}
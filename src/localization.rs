@@ -0,0 +1,368 @@
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, AsyncReadExt, LoadContext};
+use bevy::prelude::*;
+use bevy_common_assets::ron::RonAssetPlugin;
+use bevy_egui::{egui, EguiContexts};
+use fluent::concurrent::FluentBundle;
+use fluent::{FluentArgs, FluentResource};
+use serde::Deserialize;
+use std::borrow::Cow;
+use std::sync::Arc;
+use thiserror::Error;
+use unic_langid::LanguageIdentifier;
+
+/// Locale used for anything the picked locale is missing
+pub const FALLBACK_LOCALE: &str = "en";
+
+/// Translates UI text with Fluent files listed in a [`LocaleRegistry`].
+///
+/// Bevy UI text is kept up to date through [`LocalizedText`], egui screens look strings up
+/// with [`Localization::text`] as they are drawn.
+pub struct LocalizationPlugin {
+    /// Registry of every locale, relative to the asset folder
+    pub registry: String,
+    /// Locale to start in, [`FALLBACK_LOCALE`] when `None`
+    pub locale: Option<String>,
+}
+
+impl Default for LocalizationPlugin {
+    fn default() -> Self {
+        Self {
+            registry: "horror.locales.ron".into(),
+            locale: None,
+        }
+    }
+}
+
+impl Plugin for LocalizationPlugin {
+    fn build(&self, app: &mut App) {
+        let registry = self.registry.clone();
+        app.add_plugins(RonAssetPlugin::<LocaleRegistry>::new(&["locales.ron"]))
+            .init_asset::<Ftl>()
+            .init_asset::<FontBytes>()
+            .register_asset_loader(FtlLoader)
+            .register_asset_loader(FontBytesLoader)
+            .insert_resource(Locale(
+                self.locale
+                    .clone()
+                    .unwrap_or_else(|| FALLBACK_LOCALE.into()),
+            ))
+            .init_resource::<Localization>()
+            .add_systems(
+                Startup,
+                move |asset_server: Res<AssetServer>, mut localization: ResMut<Localization>| {
+                    localization.registry = asset_server.load(&registry);
+                },
+            )
+            .add_systems(
+                Update,
+                (
+                    load_locales.run_if(on_event::<AssetEvent<LocaleRegistry>>()),
+                    build_bundles.run_if(
+                        resource_changed::<Locale>
+                            .or_else(on_event::<AssetEvent<LocaleRegistry>>())
+                            .or_else(on_event::<AssetEvent<Ftl>>())
+                            .or_else(on_event::<AssetEvent<FontBytes>>()),
+                    ),
+                    (update_localized_text, set_egui_fonts)
+                        .run_if(resource_changed::<Localization>),
+                    update_changed_text,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// The locale picked by the player, by id in the [`LocaleRegistry`]
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct Locale(pub String);
+
+#[derive(Asset, TypePath, Deserialize, Debug)]
+pub struct LocaleRegistry {
+    pub locales: Vec<LocaleInfo>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct LocaleInfo {
+    /// Language identifier, e.g. `en` or `ja`
+    pub id: String,
+    /// Shown in the language picker, in the language itself
+    pub name: String,
+    /// Fluent file with every string
+    pub file: String,
+    /// Font covering the script, for languages the default fonts can't draw
+    #[serde(default)]
+    pub font: Option<String>,
+}
+
+/// A parsed Fluent file
+#[derive(Asset, TypePath, Debug)]
+pub struct Ftl(pub Arc<FluentResource>);
+
+/// Raw font file, egui takes fonts as bytes rather than as a Bevy [`Font`]
+#[derive(Asset, TypePath, Debug)]
+pub struct FontBytes(pub Vec<u8>);
+
+#[derive(Default)]
+pub struct FtlLoader;
+
+#[derive(Debug, Error)]
+pub enum FtlLoaderError {
+    #[error("could not read Fluent file: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl AssetLoader for FtlLoader {
+    type Asset = Ftl;
+    type Settings = ();
+    type Error = FtlLoaderError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Ftl, Self::Error> {
+        let mut source = String::new();
+        reader.read_to_string(&mut source).await?;
+        // keep whatever did parse, a typo shouldn't lose the whole language
+        let resource = FluentResource::try_new(source).unwrap_or_else(|(resource, errors)| {
+            for error in errors {
+                warn!("{}: {error}", load_context.path().display());
+            }
+            resource
+        });
+        Ok(Ftl(Arc::new(resource)))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ftl"]
+    }
+}
+
+#[derive(Default)]
+pub struct FontBytesLoader;
+
+impl AssetLoader for FontBytesLoader {
+    type Asset = FontBytes;
+    type Settings = ();
+    type Error = std::io::Error;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<FontBytes, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(FontBytes(bytes))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ttf", "otf"]
+    }
+}
+
+struct LocaleAssets {
+    info: LocaleInfo,
+    file: Handle<Ftl>,
+    font: Option<(Handle<Font>, Handle<FontBytes>)>,
+}
+
+/// Strings of the current locale, falling back to [`FALLBACK_LOCALE`] and then to the key itself
+#[derive(Resource, Default)]
+pub struct Localization {
+    registry: Handle<LocaleRegistry>,
+    locales: Vec<LocaleAssets>,
+    // current locale first, then the fallback
+    bundles: Vec<FluentBundle<Arc<FluentResource>>>,
+    font: Option<(Handle<Font>, Handle<FontBytes>)>,
+}
+
+impl Localization {
+    /// Every locale in the registry, as id and name
+    pub fn locales(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.locales
+            .iter()
+            .map(|locale| (locale.info.id.as_str(), locale.info.name.as_str()))
+    }
+
+    pub fn text(&self, key: &str) -> Cow<'_, str> {
+        self.format(key, None)
+    }
+
+    pub fn text_with(&self, key: &str, args: &FluentArgs) -> Cow<'_, str> {
+        self.format(key, Some(args))
+    }
+
+    fn format(&self, key: &str, args: Option<&FluentArgs>) -> Cow<'_, str> {
+        for bundle in &self.bundles {
+            let Some(pattern) = bundle.get_message(key).and_then(|message| message.value()) else {
+                continue;
+            };
+            let mut errors = Vec::new();
+            let text = bundle.format_pattern(pattern, args, &mut errors);
+            for error in errors {
+                warn!("{key}: {error}");
+            }
+            return text;
+        }
+        Cow::Owned(key.to_string())
+    }
+
+    /// Font for Bevy UI text in the current locale, the default font when `None`
+    pub fn font(&self) -> Option<&Handle<Font>> {
+        self.font.as_ref().map(|(font, _)| font)
+    }
+
+    /// egui's fonts with the current locale's after them, so it only fills in glyphs they're missing
+    pub fn egui_fonts(&self, font_bytes: &Assets<FontBytes>) -> egui::FontDefinitions {
+        let mut fonts = egui::FontDefinitions::default();
+        let bytes = self
+            .font
+            .as_ref()
+            .and_then(|(_, bytes)| font_bytes.get(bytes));
+        if let Some(bytes) = bytes {
+            fonts
+                .font_data
+                .insert("locale".into(), egui::FontData::from_owned(bytes.0.clone()));
+            for family in [egui::FontFamily::Proportional, egui::FontFamily::Monospace] {
+                fonts
+                    .families
+                    .entry(family)
+                    .or_default()
+                    .push("locale".into());
+            }
+        }
+        fonts
+    }
+
+    /// Build from already parsed Fluent sources, current locale first
+    pub fn from_sources<'a>(sources: impl IntoIterator<Item = (&'a str, &'a str)>) -> Self {
+        Self {
+            bundles: sources
+                .into_iter()
+                .map(|(id, source)| {
+                    let resource = FluentResource::try_new(source.to_string())
+                        .unwrap_or_else(|(resource, _)| resource);
+                    bundle(id, Arc::new(resource))
+                })
+                .collect(),
+            ..default()
+        }
+    }
+}
+
+fn bundle(id: &str, resource: Arc<FluentResource>) -> FluentBundle<Arc<FluentResource>> {
+    let language = id.parse::<LanguageIdentifier>().unwrap_or_else(|error| {
+        warn!("{id} is not a valid language identifier: {error}");
+        LanguageIdentifier::default()
+    });
+    let mut bundle = FluentBundle::new_concurrent(vec![language]);
+    // directional isolation marks aren't in the default fonts and show up as boxes
+    bundle.set_use_isolating(false);
+    if let Err(errors) = bundle.add_resource(resource) {
+        for error in errors {
+            warn!("{id}: {error}");
+        }
+    }
+    bundle
+}
+
+fn load_locales(
+    asset_server: Res<AssetServer>,
+    registries: Res<Assets<LocaleRegistry>>,
+    mut localization: ResMut<Localization>,
+) {
+    let Some(registry) = registries.get(&localization.registry) else {
+        return;
+    };
+    localization.locales = registry
+        .locales
+        .iter()
+        .map(|info| LocaleAssets {
+            info: info.clone(),
+            file: asset_server.load(&info.file),
+            font: info
+                .font
+                .as_ref()
+                .map(|font| (asset_server.load(font), asset_server.load(font))),
+        })
+        .collect();
+}
+
+fn build_bundles(
+    locale: Res<Locale>,
+    files: Res<Assets<Ftl>>,
+    mut localization: ResMut<Localization>,
+) {
+    let find = |id: &str| {
+        localization
+            .locales
+            .iter()
+            .find(|locale| locale.info.id == id)
+    };
+    let current = find(&locale.0);
+    if current.is_none() && !localization.locales.is_empty() {
+        warn!("no locale {}, using {FALLBACK_LOCALE}", locale.0);
+    }
+
+    let bundles = [current, find(FALLBACK_LOCALE)]
+        .into_iter()
+        .flatten()
+        .filter_map(|locale| {
+            let file = files.get(&locale.file)?;
+            Some(bundle(&locale.info.id, file.0.clone()))
+        })
+        .collect();
+    localization.font = current.and_then(|locale| locale.font.clone());
+    localization.bundles = bundles;
+}
+
+/// Bevy UI text showing the string under this key, updated whenever the locale changes.
+/// Spawn it with the English text so something sensible shows before the locale loads.
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
+pub struct LocalizedText(pub &'static str);
+
+fn set_text(localization: &Localization, key: &LocalizedText, text: &mut Text) {
+    // keep the text it was spawned with until there is something to translate it with
+    if localization.bundles.is_empty() {
+        return;
+    }
+    let Some(section) = text.sections.first_mut() else {
+        return;
+    };
+    section.value = localization.text(key.0).into_owned();
+    section.style.font = localization.font().cloned().unwrap_or_default();
+}
+
+fn update_localized_text(
+    localization: Res<Localization>,
+    mut query: Query<(&LocalizedText, &mut Text)>,
+) {
+    for (key, mut text) in query.iter_mut() {
+        set_text(&localization, key, &mut text);
+    }
+}
+
+// Text spawned or switched to another key since the last frame
+fn update_changed_text(
+    localization: Res<Localization>,
+    mut query: Query<(&LocalizedText, &mut Text), Changed<LocalizedText>>,
+) {
+    for (key, mut text) in query.iter_mut() {
+        set_text(&localization, key, &mut text);
+    }
+}
+
+fn set_egui_fonts(
+    mut contexts: EguiContexts,
+    localization: Res<Localization>,
+    font_bytes: Res<Assets<FontBytes>>,
+) {
+    let Some(ctx) = contexts.try_ctx_mut() else {
+        return;
+    };
+    ctx.set_fonts(localization.egui_fonts(&font_bytes));
+}
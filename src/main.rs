@@ -1,23 +1,10 @@
-#[cfg(feature = "debug")]
-mod debug;
-mod game;
-mod menu;
-mod splash;
-
 use bevy::{asset::AssetMetaCheck, prelude::*};
-
-pub const GAME_NAME: &str = "Jophus' Horror";
-
-// Enum that will be used as a global state for the game
-#[derive(Clone, Eq, PartialEq, Debug, Hash, Default, States)]
-enum GameState {
-    #[default]
-    Splash,
-    Menu,
-    Game,
-}
+use clap::Parser;
+use horror::cli::Args;
 
 fn main() {
+    let args = Args::parse();
+
     App::new()
         .add_plugins(
             DefaultPlugins
@@ -25,24 +12,13 @@ fn main() {
                 .set(AssetPlugin {
                     meta_check: AssetMetaCheck::Never,
                     ..default()
+                })
+                .set(WindowPlugin {
+                    primary_window: Some(args.window()),
+                    ..default()
                 }),
         )
-        // Declare the game state
-        .init_state::<GameState>()
-        // Adds the plugins for each state
-        .add_plugins((
-            splash::SplashPlugin,
-            menu::MenuPlugin,
-            game::GamePlugin,
-            #[cfg(feature = "debug")]
-            debug::DebugPlugin,
-        ))
+        .insert_resource(args.volume())
+        .add_plugins(args.plugins())
         .run();
 }
-
-// Generic system that takes a component as a parameter, and will despawn all entities with that component
-pub fn despawn_screen<T: Component>(to_despawn: Query<Entity, With<T>>, mut commands: Commands) {
-    for entity in &to_despawn {
-        commands.entity(entity).despawn_recursive();
-    }
-}
@@ -1,50 +1,80 @@
-use crate::GAME_NAME;
+use crate::accessibility::{settings_ui, Accessibility};
+#[cfg(debug_assertions)]
+use crate::assets::LevelAssets;
+#[cfg(debug_assertions)]
+use crate::game::level::{LevelRegistry, StartLevel};
+use crate::localization::{Locale, Localization};
+use crate::osd::{self, Backdrop, OsdMenu};
 
 use super::{despawn_screen, GameState};
 
 use bevy::prelude::*;
-use bevy_egui::{egui, EguiContexts, EguiPlugin};
 
 pub struct MenuPlugin;
 
 impl Plugin for MenuPlugin {
     fn build(&self, app: &mut App) {
-        if !app.is_plugin_added::<EguiPlugin>() {
-            app.add_plugins(EguiPlugin);
-        }
-        app.add_systems(Update, ui.run_if(in_state(GameState::Menu)))
+        app.add_systems(OnEnter(GameState::Menu), osd::spawn_static::<OnMenuScreen>)
+            .add_systems(Update, ui.run_if(in_state(GameState::Menu)))
             .add_systems(OnExit(GameState::Menu), despawn_screen::<OnMenuScreen>);
     }
 }
 
-#[derive(Component)]
+#[derive(Component, Default)]
 struct OnMenuScreen;
 
 fn ui(
-    mut contexts: EguiContexts,
+    mut menu: OsdMenu,
+    mut accessibility: ResMut<Accessibility>,
+    localization: Res<Localization>,
+    mut locale: ResMut<Locale>,
+    mut settings: Local<bool>,
     mut next_state: ResMut<NextState<GameState>>,
     mut writer: EventWriter<AppExit>,
+    #[cfg(debug_assertions)] mut start_level: ResMut<StartLevel>,
+    #[cfg(debug_assertions)] levels: (Res<LevelAssets>, Res<Assets<LevelRegistry>>),
 ) {
-    let ctx = contexts.ctx_mut();
-
-    egui::CentralPanel::default().show(ctx, |ui| {
-        ui.with_layout(egui::Layout::top_down(egui::Align::Center), |ui| {
-            ui.style_mut().spacing.item_spacing = egui::vec2(0.0, 10.0);
+    if *settings {
+        menu.show("settings", Backdrop::Dim, |ui, focus| {
+            let mut edited = accessibility.clone();
+            *settings = !settings_ui(ui, focus, &localization, &mut locale, &mut edited);
+            accessibility.set_if_neq(edited);
+        });
+        return;
+    }
 
-            ui.add(egui::Label::new(egui::RichText::new(GAME_NAME).size(64.0)));
+    menu.show("menu", Backdrop::Dim, |ui, focus| {
+        let text = |key: &str| localization.text(key);
+        ui.label(osd::text(text("game-name"), 64.0));
 
-            ui.add_space(10.0);
+        ui.add_space(10.0);
 
-            let play = ui.add(egui::Button::new(egui::RichText::new("Play").size(32.0)));
-            let quit = ui.add(egui::Button::new(egui::RichText::new("Quit").size(24.0)));
+        let play = focus.button(ui, osd::text(text("menu-play"), 32.0));
+        *settings = focus.button(ui, osd::text(text("menu-setup"), 24.0));
+        let quit = focus.button(ui, osd::text(text("menu-quit"), 24.0));
 
-            if play.clicked() {
-                next_state.set(GameState::Game);
+        // jump straight into any level while developing
+        #[cfg(debug_assertions)]
+        if let Some(registry) = levels.1.get(&levels.0.registry) {
+            let options: Vec<&str> = std::iter::once("default")
+                .chain(registry.levels.iter().map(|level| level.name.as_str()))
+                .collect();
+            let mut selected = start_level
+                .0
+                .as_ref()
+                .and_then(|name| options.iter().position(|option| option == name))
+                .unwrap_or(0);
+            if focus.choice(ui, &text("menu-level"), &mut selected, &options) {
+                start_level.0 = (selected > 0).then(|| options[selected].to_string());
             }
+        }
 
-            if quit.clicked() {
-                writer.send(AppExit::Success);
-            }
-        })
+        if play {
+            next_state.set(GameState::Game);
+        }
+
+        if quit {
+            writer.send(AppExit::Success);
+        }
     });
 }
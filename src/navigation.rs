@@ -0,0 +1,210 @@
+use bevy::prelude::*;
+use bevy_egui::egui;
+use leafwing_input_manager::prelude::*;
+
+/// Moves focus through egui menus with the keyboard or a gamepad, see [`MenuFocus`]
+pub struct NavigationPlugin;
+
+impl Plugin for NavigationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(InputManagerPlugin::<MenuAction>::default())
+            .init_resource::<ActionState<MenuAction>>()
+            .insert_resource(MenuAction::input_map())
+            .init_resource::<MenuFocus>();
+    }
+}
+
+#[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Reflect)]
+pub enum MenuAction {
+    Up,
+    Down,
+    Left,
+    Right,
+    Confirm,
+    Back,
+}
+
+impl MenuAction {
+    pub fn input_map() -> InputMap<Self> {
+        InputMap::new([
+            (Self::Up, KeyCode::ArrowUp),
+            (Self::Down, KeyCode::ArrowDown),
+            (Self::Left, KeyCode::ArrowLeft),
+            (Self::Right, KeyCode::ArrowRight),
+            (Self::Confirm, KeyCode::Enter),
+            (Self::Back, KeyCode::Escape),
+        ])
+        .with_multiple([
+            (Self::Up, GamepadButtonType::DPadUp),
+            (Self::Down, GamepadButtonType::DPadDown),
+            (Self::Left, GamepadButtonType::DPadLeft),
+            (Self::Right, GamepadButtonType::DPadRight),
+            (Self::Confirm, GamepadButtonType::South),
+            (Self::Back, GamepadButtonType::East),
+        ])
+        .with_multiple([
+            (Self::Up, GamepadControlDirection::LEFT_UP),
+            (Self::Down, GamepadControlDirection::LEFT_DOWN),
+            (Self::Left, GamepadControlDirection::LEFT_LEFT),
+            (Self::Right, GamepadControlDirection::LEFT_RIGHT),
+        ])
+    }
+}
+
+/// Which entry of the menu on screen has focus.
+///
+/// Menus call [`MenuFocus::begin`] once a frame, then lay out their entries through
+/// [`MenuFocus::button`] and [`MenuFocus::choice`] so focus follows the order they are drawn in.
+#[derive(Resource, Default, Debug)]
+pub struct MenuFocus {
+    screen: &'static str,
+    index: usize,
+    // entries drawn last frame, focus wraps around at this
+    len: usize,
+    // entries drawn so far this frame
+    drawn: usize,
+    confirm: bool,
+    back: bool,
+    // -1 or 1 when left or right was pressed this frame
+    adjust: i32,
+    cue: Option<MenuCue>,
+}
+
+/// Something worth giving feedback on, e.g. with a sound
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MenuCue {
+    Move,
+    Confirm,
+    Back,
+}
+
+impl MenuFocus {
+    /// Start laying out `screen` for this frame, applying whatever navigation was pressed.
+    /// Focus goes back to the first entry whenever a different screen is shown.
+    pub fn begin(&mut self, screen: &'static str, actions: &ActionState<MenuAction>) {
+        if self.screen != screen {
+            *self = Self {
+                screen,
+                ..default()
+            };
+        } else {
+            self.len = self.drawn;
+        }
+        self.drawn = 0;
+        // the menu may have lost entries since focus was last moved
+        if self.index >= self.len {
+            self.index = 0;
+        }
+
+        let index = self.index;
+        if self.len > 0 {
+            if actions.just_pressed(&MenuAction::Down) {
+                self.index = (self.index + 1) % self.len;
+            }
+            if actions.just_pressed(&MenuAction::Up) {
+                self.index = (self.index + self.len - 1) % self.len;
+            }
+        }
+        if self.index != index {
+            self.cue = Some(MenuCue::Move);
+        }
+        self.confirm = actions.just_pressed(&MenuAction::Confirm);
+        self.back = actions.just_pressed(&MenuAction::Back);
+        self.adjust = actions.just_pressed(&MenuAction::Right) as i32
+            - actions.just_pressed(&MenuAction::Left) as i32;
+    }
+
+    /// Forget the screen shown, so focus starts over even when the same screen is shown again
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Back was pressed this frame
+    pub fn back(&mut self) -> bool {
+        if self.back {
+            self.cue = Some(MenuCue::Back);
+        }
+        self.back
+    }
+
+    /// The latest cue since this was last called
+    pub fn take_cue(&mut self) -> Option<MenuCue> {
+        self.cue.take()
+    }
+
+    /// A button that is pressed by clicking it or confirming while it has focus
+    pub fn button(&mut self, ui: &mut egui::Ui, text: egui::RichText) -> bool {
+        let focused = self.next_entry();
+        let response = ui.add(focus_style(ui, egui::Button::new(text), focused));
+        if focused {
+            response.scroll_to_me(None);
+        }
+        self.hover(&response);
+        let pressed = response.clicked() || (focused && self.confirm);
+        if pressed {
+            self.cue = Some(MenuCue::Confirm);
+        }
+        pressed
+    }
+
+    /// Cycle through `options` with left and right while focused, or by clicking.
+    /// Returns whether `selected` changed.
+    pub fn choice(
+        &mut self,
+        ui: &mut egui::Ui,
+        label: &str,
+        selected: &mut usize,
+        options: &[&str],
+    ) -> bool {
+        let focused = self.next_entry();
+        let current = options.get(*selected).copied().unwrap_or_default();
+        let text = egui::RichText::new(format!("{label}: < {current} >")).size(24.0);
+        let response = ui.add(focus_style(ui, egui::Button::new(text), focused));
+        self.hover(&response);
+
+        let step = match (focused, response.clicked()) {
+            (_, true) => 1,
+            (true, false) if self.confirm => 1,
+            (true, false) => self.adjust,
+            (false, false) => 0,
+        };
+        if step == 0 || options.is_empty() {
+            return false;
+        }
+        let len = options.len() as i32;
+        *selected = (*selected as i32 + step).rem_euclid(len) as usize;
+        self.cue = Some(MenuCue::Confirm);
+        true
+    }
+
+    fn next_entry(&mut self) -> bool {
+        let focused = self.drawn == self.index;
+        self.drawn += 1;
+        focused
+    }
+
+    // the mouse moves focus too, so keyboard and mouse never disagree on what's highlighted
+    fn hover(&mut self, response: &egui::Response) {
+        let moved = response
+            .ctx
+            .input(|input| input.pointer.delta() != egui::Vec2::ZERO);
+        if response.hovered() && moved && self.index != self.drawn - 1 {
+            self.index = self.drawn - 1;
+            self.cue = Some(MenuCue::Move);
+        }
+    }
+}
+
+// highlighted with the selection colours, so menus can be themed through `egui::Visuals`
+fn focus_style<'a>(ui: &egui::Ui, button: egui::Button<'a>, focused: bool) -> egui::Button<'a> {
+    if focused {
+        let selection = ui.visuals().selection;
+        button.stroke(selection.stroke).fill(selection.bg_fill)
+    } else {
+        button
+    }
+}
@@ -0,0 +1,366 @@
+use bevy::audio::{AddAudioSource, Decodable, Source};
+use bevy::core::FrameCount;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::time::Real;
+use bevy::utils::Duration;
+use bevy_egui::{egui, EguiContexts};
+use leafwing_input_manager::prelude::*;
+
+use crate::accessibility::Accessibility;
+#[cfg(feature = "shaders")]
+use crate::game::vhs::{VHSShader, VhsPreset};
+use crate::navigation::{MenuAction, MenuCue, MenuFocus};
+
+/// Light blue of a camcorder's on-screen display
+pub const OSD_BLUE: egui::Color32 = egui::Color32::from_rgb(110, 160, 255);
+// the blue screen a VCR shows without a signal, menus fade in from it
+const BLUE_SCREEN: egui::Color32 = egui::Color32::from_rgb(16, 32, 160);
+const FADE_SECS: f32 = 0.4;
+const SAMPLE_RATE: u32 = 44_100;
+
+/// Camcorder styled menus, drawn with [`OsdMenu`] and beeping as they are navigated
+pub struct OsdPlugin;
+
+impl Plugin for OsdPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_audio_source::<Beep>()
+            .add_audio_source::<Hiss>()
+            .init_resource::<MenuBeeps>()
+            .add_systems(PostUpdate, play_cues);
+    }
+}
+
+/// Everything a menu screen needs to draw itself in the OSD style.
+///
+/// Every screen is its own system, so re-entering one is noticed by the frame it was last shown.
+#[derive(SystemParam)]
+pub struct OsdMenu<'w, 's> {
+    contexts: EguiContexts<'w, 's>,
+    focus: ResMut<'w, MenuFocus>,
+    actions: Res<'w, ActionState<MenuAction>>,
+    time: Res<'w, Time<Real>>,
+    frame: Res<'w, FrameCount>,
+    accessibility: Res<'w, Accessibility>,
+    // frame this screen was last shown and for how long it has been showing
+    shown: Local<'s, (u32, f32)>,
+}
+
+/// What a menu is drawn over
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backdrop {
+    /// Darkened, for a static background or nothing at all
+    Dim,
+    /// The game's last frame, lined and jittering like a paused tape
+    Frozen,
+}
+
+impl OsdMenu<'_, '_> {
+    /// Show `screen` over the whole window, fading in from a VCR blue screen
+    pub fn show(
+        &mut self,
+        screen: &'static str,
+        backdrop: Backdrop,
+        add_contents: impl FnOnce(&mut egui::Ui, &mut MenuFocus),
+    ) {
+        let (last_frame, shown_for) = &mut *self.shown;
+        if self.frame.0 != last_frame.wrapping_add(1) {
+            *shown_for = 0.0;
+            self.focus.reset();
+        }
+        *last_frame = self.frame.0;
+        *shown_for += self.time.delta_seconds();
+
+        self.focus.begin(screen, &self.actions);
+
+        let fade = (*shown_for / FADE_SECS).min(1.0);
+        let background = match backdrop {
+            Backdrop::Dim => egui::Color32::from_black_alpha(160),
+            Backdrop::Frozen => egui::Color32::from_black_alpha(60),
+        };
+        let frame = egui::Frame::none()
+            .fill(mix(BLUE_SCREEN, background, fade))
+            .inner_margin(egui::Margin::same(40.0));
+
+        // no window to draw into, e.g. when running headless
+        let Some(ctx) = self.contexts.try_ctx_mut() else {
+            return;
+        };
+        if backdrop == Backdrop::Frozen {
+            let jitter = match self.accessibility.reduce_motion {
+                true => 0.0,
+                false => (*shown_for * 37.0).sin() * 6.0,
+            };
+            paused_tape(ctx, jitter);
+        }
+
+        let focus = &mut *self.focus;
+        egui::CentralPanel::default().frame(frame).show(ctx, |ui| {
+            style(ui.style_mut());
+            ui.with_layout(egui::Layout::top_down(egui::Align::Center), |ui| {
+                ui.style_mut().spacing.item_spacing = egui::vec2(0.0, 10.0);
+                add_contents(ui, focus);
+            });
+        });
+    }
+
+    /// egui texture showing `image`, to draw it in a menu
+    pub fn image(&mut self, image: &Handle<Image>) -> egui::TextureId {
+        self.contexts.add_image(image.clone_weak())
+    }
+}
+
+// Scanlines over the whole screen, with a band of noise jittering near the bottom
+fn paused_tape(ctx: &egui::Context, jitter: f32) {
+    let painter = ctx.layer_painter(egui::LayerId::background());
+    let screen = ctx.screen_rect();
+    let lines = egui::Color32::from_black_alpha(70);
+    let mut y = screen.top();
+    while y < screen.bottom() {
+        painter.hline(screen.x_range(), y, egui::Stroke::new(1.0, lines));
+        y += 3.0;
+    }
+
+    let band = egui::Rect::from_min_size(
+        egui::pos2(screen.left(), screen.bottom() - 60.0 + jitter),
+        egui::vec2(screen.width(), 10.0),
+    );
+    painter.rect_filled(band, 0.0, egui::Color32::from_white_alpha(40));
+}
+
+/// OSD text, monospace in a light blue
+pub fn text(text: impl Into<String>, size: f32) -> egui::RichText {
+    egui::RichText::new(text)
+        .font(egui::FontId::monospace(size))
+        .color(OSD_BLUE)
+}
+
+fn style(style: &mut egui::Style) {
+    let visuals = &mut style.visuals;
+    visuals.selection.bg_fill = BLUE_SCREEN;
+    visuals.selection.stroke = egui::Stroke::new(2.0, egui::Color32::WHITE);
+    for widget in [
+        &mut visuals.widgets.inactive,
+        &mut visuals.widgets.hovered,
+        &mut visuals.widgets.active,
+    ] {
+        widget.weak_bg_fill = egui::Color32::TRANSPARENT;
+        widget.bg_stroke = egui::Stroke::NONE;
+        widget.rounding = egui::Rounding::ZERO;
+        widget.expansion = 0.0;
+    }
+    style.spacing.button_padding = egui::vec2(16.0, 4.0);
+}
+
+fn mix(from: egui::Color32, to: egui::Color32, t: f32) -> egui::Color32 {
+    (egui::Rgba::from(from) * (1.0 - t) + egui::Rgba::from(to) * t).into()
+}
+
+/// Spawn a camera behind a menu, rolling with tape static when the VHS shader is available.
+/// Tagged with `T` so the screen despawns it along with everything else.
+pub fn spawn_static<T: Component + Default>(
+    mut commands: Commands,
+    #[cfg(feature = "shaders")] mut meshes: ResMut<Assets<Mesh>>,
+    #[cfg(feature = "shaders")] mut images: ResMut<Assets<Image>>,
+    #[cfg(feature = "shaders")] materials: Option<ResMut<Assets<VHSShader>>>,
+    #[cfg(feature = "shaders")] preset: Option<Res<VhsPreset>>,
+    #[cfg(feature = "shaders")] accessibility: Res<Accessibility>,
+) {
+    commands.spawn((
+        Camera3dBundle {
+            camera: Camera {
+                clear_color: ClearColorConfig::Custom(Color::BLACK),
+                ..default()
+            },
+            ..default()
+        },
+        T::default(),
+    ));
+
+    // the shader adds its own noise on top of a plain dark image
+    #[cfg(feature = "shaders")]
+    if let Some(mut materials) = materials {
+        let image = Image::new_fill(
+            bevy::render::render_resource::Extent3d::default(),
+            bevy::render::render_resource::TextureDimension::D2,
+            &[4, 8, 32, 255],
+            bevy::render::render_resource::TextureFormat::Rgba8UnormSrgb,
+            bevy::render::render_asset::RenderAssetUsages::RENDER_WORLD,
+        );
+        commands.spawn((
+            MaterialMeshBundle {
+                // big enough to fill the view at any common aspect ratio
+                mesh: meshes.add(Rectangle::new(4.0, 4.0)),
+                material: materials.add(VHSShader {
+                    color: preset.map_or(LinearRgba::WHITE, |preset| preset.tint()),
+                    img: images.add(image),
+                    noise: accessibility.vhs_noise(),
+                }),
+                transform: Transform::from_xyz(0.0, 0.0, -1.0),
+                ..default()
+            },
+            T::default(),
+        ));
+    }
+}
+
+/// Short square wave tone, like a camcorder's button beep
+#[derive(Asset, TypePath, Debug, Clone)]
+pub struct Beep {
+    pub frequency: f32,
+    pub duration: Duration,
+}
+
+impl Decodable for Beep {
+    type DecoderItem = f32;
+    type Decoder = BeepDecoder;
+
+    fn decoder(&self) -> Self::Decoder {
+        BeepDecoder {
+            frequency: self.frequency,
+            sample: 0,
+            samples: (self.duration.as_secs_f32() * SAMPLE_RATE as f32) as u32,
+        }
+    }
+}
+
+pub struct BeepDecoder {
+    frequency: f32,
+    sample: u32,
+    samples: u32,
+}
+
+impl Iterator for BeepDecoder {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if self.sample >= self.samples {
+            return None;
+        }
+        let t = self.sample as f32 / SAMPLE_RATE as f32;
+        // fade out so the tone doesn't end with a click
+        let envelope = 1.0 - self.sample as f32 / self.samples as f32;
+        self.sample += 1;
+        let square = if (t * self.frequency).fract() < 0.5 {
+            1.0
+        } else {
+            -1.0
+        };
+        Some(square * envelope * 0.15)
+    }
+}
+
+impl Source for BeepDecoder {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        SAMPLE_RATE
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+/// Endless soft tape hiss, for ambience behind menus
+#[derive(Asset, TypePath, Debug, Clone)]
+pub struct Hiss {
+    pub volume: f32,
+}
+
+impl Decodable for Hiss {
+    type DecoderItem = f32;
+    type Decoder = HissDecoder;
+
+    fn decoder(&self) -> Self::Decoder {
+        HissDecoder {
+            volume: self.volume,
+            state: 0x2545_f491,
+            last: 0.0,
+        }
+    }
+}
+
+pub struct HissDecoder {
+    volume: f32,
+    // xorshift, audio noise has no need for the seeded `GameRng`
+    state: u32,
+    last: f32,
+}
+
+impl Iterator for HissDecoder {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 17;
+        self.state ^= self.state << 5;
+        let noise = self.state as f32 / u32::MAX as f32 * 2.0 - 1.0;
+        // low pass, white noise is harsher than a tape sounds
+        self.last += (noise - self.last) * 0.3;
+        Some(self.last * self.volume)
+    }
+}
+
+impl Source for HissDecoder {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        SAMPLE_RATE
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+#[derive(Resource)]
+struct MenuBeeps {
+    moved: Handle<Beep>,
+    confirm: Handle<Beep>,
+    back: Handle<Beep>,
+}
+
+impl FromWorld for MenuBeeps {
+    fn from_world(world: &mut World) -> Self {
+        let mut beeps = world.resource_mut::<Assets<Beep>>();
+        let mut beep = |frequency, millis| {
+            beeps.add(Beep {
+                frequency,
+                duration: Duration::from_millis(millis),
+            })
+        };
+        Self {
+            moved: beep(1200.0, 30),
+            confirm: beep(1600.0, 90),
+            back: beep(800.0, 90),
+        }
+    }
+}
+
+fn play_cues(mut commands: Commands, mut focus: ResMut<MenuFocus>, beeps: Res<MenuBeeps>) {
+    let Some(cue) = focus.bypass_change_detection().take_cue() else {
+        return;
+    };
+    let source = match cue {
+        MenuCue::Move => &beeps.moved,
+        MenuCue::Confirm => &beeps.confirm,
+        MenuCue::Back => &beeps.back,
+    };
+    commands.spawn(AudioSourceBundle {
+        source: source.clone(),
+        settings: PlaybackSettings::DESPAWN,
+    });
+}
@@ -0,0 +1,195 @@
+use bevy::diagnostic::{
+    Diagnostic, DiagnosticPath, Diagnostics, DiagnosticsStore, EntityCountDiagnosticsPlugin,
+    FrameTimeDiagnosticsPlugin, RegisterDiagnostic,
+};
+use bevy::prelude::*;
+use bevy::time::Real;
+use bevy::utils::{Duration, Instant};
+use bevy_egui::{egui, EguiContexts};
+use bevy_rapier3d::prelude::*;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use crate::debug::DebugTools;
+
+pub const RAPIER_STEP: DiagnosticPath = DiagnosticPath::const_new("horror/rapier_step");
+pub const COLLIDERS: DiagnosticPath = DiagnosticPath::const_new("horror/colliders");
+pub const LIGHTS: DiagnosticPath = DiagnosticPath::const_new("horror/lights");
+/// Meshes visible from any view, standing in for draw calls which bevy doesn't report
+pub const VISIBLE_MESHES: DiagnosticPath = DiagnosticPath::const_new("horror/visible_meshes");
+
+// Every diagnostic shown and logged, in column order
+const COLUMNS: [(&str, DiagnosticPath); 7] = [
+    ("frame time (ms)", FrameTimeDiagnosticsPlugin::FRAME_TIME),
+    ("fps", FrameTimeDiagnosticsPlugin::FPS),
+    ("entities", EntityCountDiagnosticsPlugin::ENTITY_COUNT),
+    ("rapier step (ms)", RAPIER_STEP),
+    ("colliders", COLLIDERS),
+    ("lights", LIGHTS),
+    ("draw calls (visible meshes)", VISIBLE_MESHES),
+];
+
+/// Performance overlay of the `debug` feature, with optional logging to CSV
+pub struct PerfPlugin;
+
+impl Plugin for PerfPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((FrameTimeDiagnosticsPlugin, EntityCountDiagnosticsPlugin))
+            .register_diagnostic(Diagnostic::new(RAPIER_STEP).with_suffix("ms"))
+            .register_diagnostic(Diagnostic::new(COLLIDERS))
+            .register_diagnostic(Diagnostic::new(LIGHTS))
+            .register_diagnostic(Diagnostic::new(VISIBLE_MESHES))
+            .init_resource::<PerfLog>()
+            .add_systems(
+                PostUpdate,
+                (
+                    start_step.before(PhysicsSet::StepSimulation),
+                    finish_step.after(PhysicsSet::StepSimulation),
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    count,
+                    overlay.run_if(|tools: Res<DebugTools>| tools.performance),
+                    log_csv,
+                )
+                    .chain(),
+            );
+    }
+}
+
+#[derive(Resource, Default)]
+struct PerfLog {
+    step_started: Option<Instant>,
+    step: Duration,
+    file: Option<BufWriter<File>>,
+    error: Option<String>,
+}
+
+fn start_step(mut log: ResMut<PerfLog>) {
+    log.step_started = Some(Instant::now());
+}
+
+fn finish_step(mut log: ResMut<PerfLog>) {
+    if let Some(started) = log.step_started.take() {
+        log.step = started.elapsed();
+    }
+}
+
+fn count(
+    mut diagnostics: Diagnostics,
+    log: Res<PerfLog>,
+    colliders: Query<(), With<Collider>>,
+    point_lights: Query<(), With<PointLight>>,
+    spot_lights: Query<(), With<SpotLight>>,
+    directional_lights: Query<(), With<DirectionalLight>>,
+    meshes: Query<&ViewVisibility, With<Handle<Mesh>>>,
+) {
+    diagnostics.add_measurement(&RAPIER_STEP, || log.step.as_secs_f64() * 1000.0);
+    diagnostics.add_measurement(&COLLIDERS, || colliders.iter().count() as f64);
+    diagnostics.add_measurement(&LIGHTS, || {
+        (point_lights.iter().count()
+            + spot_lights.iter().count()
+            + directional_lights.iter().count()) as f64
+    });
+    diagnostics.add_measurement(&VISIBLE_MESHES, || {
+        meshes.iter().filter(|visibility| visibility.get()).count() as f64
+    });
+}
+
+fn overlay(mut contexts: EguiContexts, store: Res<DiagnosticsStore>, mut log: ResMut<PerfLog>) {
+    egui::Window::new("Performance")
+        .anchor(egui::Align2::LEFT_BOTTOM, egui::vec2(10.0, -10.0))
+        .resizable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            if let Some(frame_time) = store.get(&FrameTimeDiagnosticsPlugin::FRAME_TIME) {
+                frame_time_graph(ui, frame_time);
+            }
+
+            egui::Grid::new("diagnostics").show(ui, |ui| {
+                for (name, path) in &COLUMNS {
+                    ui.label(*name);
+                    let value = store.get(path).and_then(|diagnostic| diagnostic.smoothed());
+                    ui.monospace(value.map_or("-".into(), |value| format!("{value:.1}")));
+                    ui.end_row();
+                }
+            });
+
+            let mut logging = log.file.is_some();
+            if ui.checkbox(&mut logging, "Log to CSV").changed() {
+                log.file = None;
+                log.error = None;
+                if logging {
+                    match start_csv() {
+                        Ok((path, file)) => {
+                            info!("Logging performance to {path}");
+                            log.file = Some(file);
+                        }
+                        Err(error) => log.error = Some(error.to_string()),
+                    }
+                }
+            }
+            if let Some(error) = &log.error {
+                ui.colored_label(egui::Color32::LIGHT_RED, error);
+            }
+        });
+}
+
+// Frame times of the last couple of seconds, with a line at 60 fps
+fn frame_time_graph(ui: &mut egui::Ui, frame_time: &Diagnostic) {
+    let (response, painter) = ui.allocate_painter(egui::vec2(240.0, 60.0), egui::Sense::hover());
+    let rect = response.rect;
+    painter.rect_filled(rect, 2.0, egui::Color32::from_black_alpha(160));
+
+    let max_ms = 50.0;
+    let y = |ms: f64| rect.bottom() - (ms / max_ms).min(1.0) as f32 * rect.height();
+    painter.hline(
+        rect.x_range(),
+        y(1000.0 / 60.0),
+        egui::Stroke::new(1.0, egui::Color32::DARK_GREEN),
+    );
+
+    let values: Vec<f64> = frame_time.values().copied().collect();
+    let step = rect.width() / values.len().max(2).saturating_sub(1) as f32;
+    let points = values
+        .iter()
+        .enumerate()
+        .map(|(index, ms)| egui::pos2(rect.left() + index as f32 * step, y(*ms)))
+        .collect();
+    painter.add(egui::Shape::line(
+        points,
+        egui::Stroke::new(1.0, egui::Color32::LIGHT_GREEN),
+    ));
+}
+
+fn start_csv() -> std::io::Result<(String, BufWriter<File>)> {
+    let seconds = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let path = format!("perf-{seconds}.csv");
+    let mut file = BufWriter::new(File::create(&path)?);
+    let header: Vec<&str> = COLUMNS.iter().map(|(name, _)| *name).collect();
+    writeln!(file, "time (s),{}", header.join(","))?;
+    Ok((path, file))
+}
+
+fn log_csv(time: Res<Time<Real>>, store: Res<DiagnosticsStore>, mut log: ResMut<PerfLog>) {
+    let Some(file) = log.file.as_mut() else {
+        return;
+    };
+    let values: Vec<String> = COLUMNS
+        .iter()
+        .map(|(_, path)| {
+            store
+                .get_measurement(path)
+                .map_or(String::new(), |measurement| measurement.value.to_string())
+        })
+        .collect();
+    if let Err(error) = writeln!(file, "{:.3},{}", time.elapsed_seconds(), values.join(",")) {
+        error!("Stopped logging performance: {error}");
+        log.file = None;
+        log.error = Some(error.to_string());
+    }
+}
@@ -0,0 +1,86 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use super::GameState;
+
+/// Environment variable read for the seed when none is given to [`RngPlugin`]
+pub const SEED_VAR: &str = "HORROR_SEED";
+
+/// Inserts the [`GameRng`] every bit of gameplay randomness draws from
+#[derive(Default)]
+pub struct RngPlugin {
+    /// Falls back to `HORROR_SEED`, then to a random seed
+    pub seed: Option<u64>,
+}
+
+impl Plugin for RngPlugin {
+    fn build(&self, app: &mut App) {
+        let seed = self
+            .seed
+            .or_else(seed_from_env)
+            .unwrap_or_else(rand::random);
+        info!("Seed {seed}");
+        app.insert_resource(GameRng::new(seed))
+            // every session with the same seed plays out the same way
+            .add_systems(OnEnter(GameState::Game), |mut rng: ResMut<GameRng>| {
+                rng.reset()
+            });
+    }
+}
+
+fn seed_from_env() -> Option<u64> {
+    let value = std::env::var(SEED_VAR).ok()?;
+    match value.parse() {
+        Ok(seed) => Some(seed),
+        Err(error) => {
+            warn!("Ignoring {SEED_VAR}={value}: {error}");
+            None
+        }
+    }
+}
+
+/// Seeded random number generator split into named streams.
+///
+/// Each system draws from its own stream, so how often one system rolls
+/// doesn't change what any other system gets. Streams are ChaCha8, whose output is fixed
+/// across platforms and rand versions unlike `ChaCha8Rng`, so shared seeds keep playing the same.
+#[derive(Resource)]
+pub struct GameRng {
+    seed: u64,
+    streams: HashMap<&'static str, ChaCha8Rng>,
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            streams: HashMap::default(),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Stream for `name`, started from the seed the first time it is used
+    pub fn stream(&mut self, name: &'static str) -> &mut ChaCha8Rng {
+        let seed = self.seed;
+        self.streams
+            .entry(name)
+            .or_insert_with(|| ChaCha8Rng::seed_from_u64(stream_seed(seed, name)))
+    }
+
+    /// Start every stream over from the seed
+    pub fn reset(&mut self) {
+        self.streams.clear();
+    }
+}
+
+// FNV-1a, unlike `Hash` it is guaranteed to stay the same across builds
+fn stream_seed(seed: u64, name: &str) -> u64 {
+    name.bytes().fold(0xcbf29ce484222325 ^ seed, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
@@ -0,0 +1,167 @@
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, AsyncReadExt, LoadContext};
+use bevy::core_pipeline::Skybox;
+use bevy::prelude::*;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{
+    Extent3d, TextureDimension, TextureFormat, TextureViewDescriptor, TextureViewDimension,
+};
+use bevy::render::texture::{CompressedImageFormats, ImageSampler, ImageType, TextureError};
+use std::f32::consts::PI;
+use thiserror::Error;
+
+/// Registers the skybox loader and animates skybox brightness.
+///
+/// Any image named `*.sky.png` or `*.sky.jpg` is converted to a cubemap once, as it loads.
+pub struct SkyboxPlugin;
+
+impl Plugin for SkyboxPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_asset_loader(SkyboxLoader)
+            .add_systems(Update, flash_skybox);
+    }
+}
+
+#[derive(Default)]
+pub struct SkyboxLoader;
+
+#[derive(Debug, Error)]
+pub enum SkyboxLoaderError {
+    #[error("could not read skybox: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("could not decode skybox: {0}")]
+    Texture(#[from] TextureError),
+    #[error("could not convert skybox: {0}")]
+    Conversion(String),
+    #[error(
+        "skybox is {0}x{1}, expected a vertical strip of 6 square faces or a 2:1 equirectangular image"
+    )]
+    Layout(u32, u32),
+}
+
+impl AssetLoader for SkyboxLoader {
+    type Asset = Image;
+    type Settings = ();
+    type Error = SkyboxLoaderError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Image, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        let extension = load_context
+            .path()
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or_default();
+        let image = Image::from_buffer(
+            &bytes,
+            ImageType::Extension(extension),
+            CompressedImageFormats::NONE,
+            true,
+            ImageSampler::Default,
+            RenderAssetUsages::RENDER_WORLD,
+        )?;
+
+        let (width, height) = (image.width(), image.height());
+        let mut cubemap = if height == width * 6 {
+            let mut image = image;
+            image.reinterpret_stacked_2d_as_array(6);
+            image
+        } else if width == height * 2 {
+            equirectangular_to_cubemap(image)?
+        } else {
+            return Err(SkyboxLoaderError::Layout(width, height));
+        };
+        cubemap.texture_view_descriptor = Some(TextureViewDescriptor {
+            dimension: Some(TextureViewDimension::Cube),
+            ..default()
+        });
+        Ok(cubemap)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["sky.png", "sky.jpg", "sky.jpeg"]
+    }
+}
+
+// Faces are ordered +X, -X, +Y, -Y, +Z, -Z, as wgpu expects
+fn equirectangular_to_cubemap(image: Image) -> Result<Image, SkyboxLoaderError> {
+    let source = image
+        .try_into_dynamic()
+        .map_err(|error| SkyboxLoaderError::Conversion(error.to_string()))?
+        .to_rgba8();
+    let size = source.height() / 2;
+    let mut data = Vec::with_capacity((size * size * 6 * 4) as usize);
+
+    for face in 0..6 {
+        for y in 0..size {
+            for x in 0..size {
+                let u = 2.0 * (x as f32 + 0.5) / size as f32 - 1.0;
+                let v = 2.0 * (y as f32 + 0.5) / size as f32 - 1.0;
+                let direction = match face {
+                    0 => Vec3::new(1.0, -v, -u),
+                    1 => Vec3::new(-1.0, -v, u),
+                    2 => Vec3::new(u, 1.0, v),
+                    3 => Vec3::new(u, -1.0, -v),
+                    4 => Vec3::new(u, -v, 1.0),
+                    _ => Vec3::new(-u, -v, -1.0),
+                }
+                .normalize();
+
+                let longitude = direction.z.atan2(direction.x);
+                let latitude = direction.y.asin();
+                let sx = ((0.5 + longitude / (2.0 * PI)) * source.width() as f32) as u32;
+                let sy = ((0.5 - latitude / PI) * source.height() as f32) as u32;
+                let pixel =
+                    source.get_pixel(sx.min(source.width() - 1), sy.min(source.height() - 1));
+                data.extend_from_slice(&pixel.0);
+            }
+        }
+    }
+
+    Ok(Image::new(
+        Extent3d {
+            width: size,
+            height: size,
+            depth_or_array_layers: 6,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    ))
+}
+
+/// Briefly raises the brightness of a [`Skybox`] above `base`, e.g. for lightning
+#[derive(Component)]
+pub struct SkyboxFlash {
+    pub base: f32,
+    pub peak: f32,
+    pub timer: Timer,
+}
+
+impl SkyboxFlash {
+    pub fn new(base: f32, peak: f32, seconds: f32) -> Self {
+        let mut timer = Timer::from_seconds(seconds, TimerMode::Once);
+        // start out idle rather than mid-flash
+        timer.tick(timer.duration());
+        Self { base, peak, timer }
+    }
+
+    pub fn trigger(&mut self) {
+        self.timer.reset();
+    }
+}
+
+fn flash_skybox(time: Res<Time>, mut query: Query<(&mut SkyboxFlash, &mut Skybox)>) {
+    for (mut flash, mut skybox) in query.iter_mut() {
+        flash.timer.tick(time.delta());
+        // fades out from the peak over the length of the timer
+        skybox.brightness = flash.base + (flash.peak - flash.base) * (1.0 - flash.timer.fraction());
+    }
+}
@@ -1,31 +1,48 @@
 use bevy::prelude::*;
-use bevy_asset_loader::prelude::*;
+use iyes_progress::prelude::*;
+use rand::Rng;
 
 use super::{despawn_screen, GameState, GAME_NAME};
-use crate::game::{AudioAssets, GltfAssets, TextureAssets};
+use crate::accessibility::{Accessibility, Flashing};
+use crate::assets::LoadingProgress;
+use crate::localization::{Localization, LocalizedText};
+use crate::rng::GameRng;
+use fluent::FluentArgs;
+
+// Keep the splash up for at least this long, even when assets load instantly or the
+// sequence is skipped
+const MINIMUM_DISPLAY_SECS: f32 = 2.0;
+const FADE_SECS: f32 = 0.5;
+// how long the cassette takes to slide into the deck
+const INSERT_SECS: f32 = 1.5;
 
 pub struct SplashPlugin;
 
 impl Plugin for SplashPlugin {
     fn build(&self, app: &mut App) {
-        // As this plugin is managing the splash screen, it will focus on the state `GameState::Splash`
-        app.add_loading_state(
-            LoadingState::new(GameState::Splash)
-                .continue_to_state(GameState::Menu)
-                .load_collection::<AudioAssets>()
-                .load_collection::<GltfAssets>()
-                .load_collection::<TextureAssets>(),
-        )
+        // As this plugin is managing the splash screen, it will focus on the state `GameState::Splash`,
+        // assets are loaded by `AssetsPlugin` while in this state
         // When entering the state, spawn everything needed for this screen
-        .add_systems(OnEnter(GameState::Splash), splash_setup)
-        // When exiting the state, despawn everything that was spawned for this screen
-        .add_systems(
-            OnExit(GameState::Splash),
-            (
-                despawn_screen::<OnSplashScreen>,
-                despawn_screen::<SplashCamera>,
-            ),
-        );
+        app.add_systems(OnEnter(GameState::Splash), splash_setup)
+            .add_systems(
+                Update,
+                (
+                    minimum_display_time.track_progress(),
+                    (skip_sequence, advance_sequence.track_progress()).chain(),
+                    update_progress,
+                    animate_tracking,
+                    animate_cassette,
+                )
+                    .run_if(in_state(GameState::Splash)),
+            )
+            // When exiting the state, despawn everything that was spawned for this screen
+            .add_systems(
+                OnExit(GameState::Splash),
+                (
+                    despawn_screen::<OnSplashScreen>,
+                    despawn_screen::<SplashCamera>,
+                ),
+            );
     }
 }
 
@@ -36,21 +53,526 @@ struct OnSplashScreen;
 #[derive(Component)]
 struct SplashCamera;
 
-fn splash_setup(mut commands: Commands) {
-    commands.spawn((Camera2dBundle::default(), SplashCamera));
+#[derive(Component)]
+struct MinimumDisplay(Timer);
+
+/// A card of the splash sequence, shown one after another
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplashCard {
+    Studio,
+    TapeInsert,
+    Warning,
+    /// Progress of every asset collection, shown until loading finishes
+    Loading,
+}
+
+impl SplashCard {
+    /// Every card in order, with how long it shows for
+    pub const SEQUENCE: [(Self, f32); 3] = [
+        (Self::Studio, 2.5),
+        (Self::TapeInsert, 3.0),
+        (Self::Warning, 4.0),
+    ];
+}
+
+/// Which card is showing and for how long, the sequence ends on `SplashCard::Loading`
+#[derive(Component, Debug, Default)]
+pub struct SplashSequence {
+    card: usize,
+    elapsed: f32,
+}
+
+impl SplashSequence {
+    pub fn tick(&mut self, delta: f32) {
+        self.elapsed += delta;
+        while let Some((_, duration)) = SplashCard::SEQUENCE.get(self.card) {
+            if self.elapsed < *duration {
+                break;
+            }
+            self.elapsed -= duration;
+            self.card += 1;
+        }
+    }
+
+    /// Jump straight to the loading card
+    pub fn skip(&mut self) {
+        if !self.finished() {
+            self.card = SplashCard::SEQUENCE.len();
+            self.elapsed = 0.0;
+        }
+    }
+
+    pub fn card(&self) -> SplashCard {
+        SplashCard::SEQUENCE
+            .get(self.card)
+            .map_or(SplashCard::Loading, |(card, _)| *card)
+    }
+
+    /// Seconds the current card has been showing
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    pub fn finished(&self) -> bool {
+        self.card() == SplashCard::Loading
+    }
+
+    /// How visible the current card is, fading in and out at either end
+    pub fn opacity(&self) -> f32 {
+        let fade_in = (self.elapsed / FADE_SECS).min(1.0);
+        match SplashCard::SEQUENCE.get(self.card) {
+            Some((_, duration)) => fade_in.min(((duration - self.elapsed) / FADE_SECS).min(1.0)),
+            None => fade_in,
+        }
+    }
+}
+
+#[derive(Component)]
+struct CardRoot(SplashCard);
+
+// Black overlay on top of every card, fading them in and out
+#[derive(Component)]
+struct Fade;
+
+#[derive(Component)]
+struct Cassette;
+
+#[derive(Component)]
+struct PlayLabel;
+
+// Index into `LoadingProgress::collections`
+#[derive(Component)]
+struct ProgressFill(usize);
+
+#[derive(Component)]
+struct ProgressCount(usize);
+
+#[derive(Component)]
+struct FailureText;
+
+// Horizontal band of noise rolling down the screen, like a tape with bad tracking
+#[derive(Component)]
+struct TrackingLine {
+    speed: f32,
+}
+
+#[derive(Component)]
+struct TrackingLabel(Timer);
 
+fn splash_setup(mut commands: Commands, progress: Res<LoadingProgress>, mut rng: ResMut<GameRng>) {
     commands.spawn((
-        TextBundle {
-            text: Text::from_section(
-                GAME_NAME,
-                TextStyle {
-                    font_size: 180.0,
-                    color: Color::BLACK,
-                    ..Default::default()
-                },
-            ),
+        Camera2dBundle {
+            camera: Camera {
+                clear_color: ClearColorConfig::Custom(Color::BLACK),
+                ..Default::default()
+            },
             ..Default::default()
         },
-        OnSplashScreen,
+        SplashCamera,
     ));
+
+    let text_style = TextStyle {
+        font_size: 32.0,
+        color: Color::WHITE,
+        ..Default::default()
+    };
+
+    let card = |which| {
+        (
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    row_gap: Val::Px(10.0),
+                    ..Default::default()
+                },
+                visibility: Visibility::Hidden,
+                ..Default::default()
+            },
+            CardRoot(which),
+        )
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    ..Default::default()
+                },
+                background_color: Color::BLACK.into(),
+                ..Default::default()
+            },
+            MinimumDisplay(Timer::from_seconds(MINIMUM_DISPLAY_SECS, TimerMode::Once)),
+            SplashSequence::default(),
+            OnSplashScreen,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(card(SplashCard::Studio))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(
+                            "A JOPHUS PRODUCTION",
+                            TextStyle {
+                                font_size: 64.0,
+                                ..text_style.clone()
+                            },
+                        ),
+                        LocalizedText("splash-studio"),
+                    ));
+                });
+
+            parent
+                .spawn(card(SplashCard::TapeInsert))
+                .with_children(|parent| {
+                    // the deck's slot, the cassette slides up into it
+                    parent.spawn(NodeBundle {
+                        style: Style {
+                            width: Val::Px(320.0),
+                            height: Val::Px(16.0),
+                            border: UiRect::all(Val::Px(2.0)),
+                            ..Default::default()
+                        },
+                        background_color: Color::BLACK.into(),
+                        border_color: Color::srgb(0.5, 0.5, 0.5).into(),
+                        ..Default::default()
+                    });
+                    parent
+                        .spawn((
+                            NodeBundle {
+                                style: Style {
+                                    position_type: PositionType::Absolute,
+                                    width: Val::Px(280.0),
+                                    height: Val::Px(170.0),
+                                    top: Val::Percent(100.0),
+                                    justify_content: JustifyContent::SpaceEvenly,
+                                    align_items: AlignItems::Center,
+                                    ..Default::default()
+                                },
+                                background_color: Color::srgb(0.12, 0.12, 0.12).into(),
+                                ..Default::default()
+                            },
+                            Cassette,
+                        ))
+                        .with_children(|cassette| {
+                            for _ in 0..2 {
+                                cassette.spawn(NodeBundle {
+                                    style: Style {
+                                        width: Val::Px(50.0),
+                                        height: Val::Px(50.0),
+                                        border: UiRect::all(Val::Px(8.0)),
+                                        ..Default::default()
+                                    },
+                                    border_color: Color::WHITE.into(),
+                                    border_radius: BorderRadius::MAX,
+                                    ..Default::default()
+                                });
+                            }
+                        });
+                    parent.spawn((
+                        TextBundle {
+                            visibility: Visibility::Hidden,
+                            ..TextBundle::from_section("PLAY \u{25B6}", text_style.clone())
+                        },
+                        LocalizedText("splash-play"),
+                        PlayLabel,
+                    ));
+                });
+
+            parent
+                .spawn(card(SplashCard::Warning))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(
+                            "WARNING",
+                            TextStyle {
+                                font_size: 72.0,
+                                color: Color::srgb(0.9, 0.1, 0.1),
+                                ..Default::default()
+                            },
+                        ),
+                        LocalizedText("splash-warning"),
+                    ));
+                    parent.spawn((
+                        TextBundle::from_section(
+                            "This tape was recovered from the house on the hill.\n\
+                             It is licensed for private home viewing only.\n\
+                             Viewing after dark is strongly discouraged.",
+                            text_style.clone(),
+                        )
+                        .with_text_justify(JustifyText::Center)
+                        .with_style(Style {
+                            max_width: Val::Px(900.0),
+                            ..Default::default()
+                        }),
+                        LocalizedText("splash-warning-body"),
+                    ));
+                });
+
+            parent
+                .spawn(card(SplashCard::Loading))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(
+                            GAME_NAME,
+                            TextStyle {
+                                font_size: 96.0,
+                                ..text_style.clone()
+                            },
+                        ),
+                        LocalizedText("game-name"),
+                    ));
+
+                    for (index, collection) in progress.collections.iter().enumerate() {
+                        parent
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    width: Val::Px(600.0),
+                                    align_items: AlignItems::Center,
+                                    column_gap: Val::Px(10.0),
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            })
+                            .with_children(|row| {
+                                row.spawn(
+                                    TextBundle::from_section(collection.name, text_style.clone())
+                                        .with_style(Style {
+                                            width: Val::Px(160.0),
+                                            ..Default::default()
+                                        }),
+                                );
+                                row.spawn(NodeBundle {
+                                    style: Style {
+                                        flex_grow: 1.0,
+                                        height: Val::Px(20.0),
+                                        border: UiRect::all(Val::Px(2.0)),
+                                        ..Default::default()
+                                    },
+                                    border_color: Color::WHITE.into(),
+                                    ..Default::default()
+                                })
+                                .with_children(|bar| {
+                                    bar.spawn((
+                                        NodeBundle {
+                                            style: Style {
+                                                width: Val::Percent(0.0),
+                                                height: Val::Percent(100.0),
+                                                ..Default::default()
+                                            },
+                                            background_color: Color::WHITE.into(),
+                                            ..Default::default()
+                                        },
+                                        ProgressFill(index),
+                                    ));
+                                });
+                                row.spawn((
+                                    TextBundle::from_section(
+                                        format!("0/{}", collection.total),
+                                        text_style.clone(),
+                                    ),
+                                    ProgressCount(index),
+                                ));
+                            });
+                    }
+
+                    parent.spawn((
+                        TextBundle::from_section(
+                            "",
+                            TextStyle {
+                                font_size: 24.0,
+                                color: Color::srgb(1.0, 0.2, 0.2),
+                                ..Default::default()
+                            },
+                        ),
+                        FailureText,
+                    ));
+                });
+
+            parent.spawn((
+                TextBundle::from_section("TRACKING", text_style.clone()).with_style(Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(5.0),
+                    left: Val::Px(5.0),
+                    ..Default::default()
+                }),
+                LocalizedText("splash-tracking"),
+                TrackingLabel(Timer::from_seconds(0.5, TimerMode::Repeating)),
+            ));
+
+            for speed in [35.0, 60.0, 90.0] {
+                parent.spawn((
+                    NodeBundle {
+                        style: Style {
+                            position_type: PositionType::Absolute,
+                            width: Val::Percent(100.0),
+                            height: Val::Px(6.0),
+                            top: Val::Percent(rng.stream("splash").gen_range(0.0..100.0)),
+                            ..Default::default()
+                        },
+                        background_color: Color::srgba(1.0, 1.0, 1.0, 0.15).into(),
+                        ..Default::default()
+                    },
+                    TrackingLine { speed },
+                ));
+            }
+
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        width: Val::Percent(100.0),
+                        height: Val::Percent(100.0),
+                        ..Default::default()
+                    },
+                    background_color: Color::BLACK.into(),
+                    ..Default::default()
+                },
+                Fade,
+            ));
+        });
+}
+
+fn minimum_display_time(time: Res<Time>, mut query: Query<&mut MinimumDisplay>) -> HiddenProgress {
+    let finished = query
+        .iter_mut()
+        .all(|mut minimum| minimum.0.tick(time.delta()).finished());
+    HiddenProgress(finished.into())
+}
+
+fn update_progress(
+    progress: Res<LoadingProgress>,
+    accessibility: Res<Accessibility>,
+    localization: Res<Localization>,
+    mut fills: Query<(&ProgressFill, &mut Style)>,
+    mut counts: Query<(&ProgressCount, &mut Text), Without<FailureText>>,
+    mut failures: Query<&mut Text, With<FailureText>>,
+) {
+    for (fill, mut style) in fills.iter_mut() {
+        if let Some(collection) = progress.collections.get(fill.0) {
+            style.width = Val::Percent(collection.fraction() * 100.0);
+        }
+    }
+    for (count, mut text) in counts.iter_mut() {
+        if let Some(collection) = progress.collections.get(count.0) {
+            text.sections[0].value = format!("{}/{}", collection.done, collection.total);
+        }
+    }
+    for mut text in failures.iter_mut() {
+        text.sections[0].style.color = accessibility.danger();
+        text.sections[0].style.font = localization.font().cloned().unwrap_or_default();
+        text.sections[0].value = progress
+            .failures()
+            .map(|(path, reason)| {
+                let args = FluentArgs::from_iter([("path", path), ("reason", reason)]);
+                localization.text_with("splash-failed", &args).into_owned()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+    }
+}
+
+fn animate_tracking(
+    time: Res<Time>,
+    mut rng: ResMut<GameRng>,
+    accessibility: Res<Accessibility>,
+    mut lines: Query<(&TrackingLine, &mut Style)>,
+    mut labels: Query<(&mut TrackingLabel, &mut Visibility)>,
+) {
+    let rng = rng.stream("splash");
+    for (line, mut style) in lines.iter_mut().filter(|_| !accessibility.reduce_motion) {
+        let Val::Percent(top) = style.top else {
+            continue;
+        };
+        style.top = Val::Percent((top + line.speed * time.delta_seconds()) % 100.0);
+        style.height = Val::Px(rng.gen_range(2.0..12.0));
+    }
+    for (mut label, mut visibility) in labels.iter_mut() {
+        if accessibility.flashing != Flashing::On {
+            *visibility = Visibility::Inherited;
+        } else if label.0.tick(time.delta()).just_finished() {
+            *visibility = match *visibility {
+                Visibility::Hidden => Visibility::Inherited,
+                _ => Visibility::Hidden,
+            };
+        }
+    }
+}
+
+// Any key, mouse or gamepad button skips to the loading card
+fn skip_sequence(
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    gamepad: Res<ButtonInput<GamepadButton>>,
+    mut query: Query<&mut SplashSequence>,
+) {
+    if keys.get_just_pressed().next().is_some()
+        || mouse.get_just_pressed().next().is_some()
+        || gamepad.get_just_pressed().next().is_some()
+    {
+        for mut sequence in query.iter_mut() {
+            sequence.skip();
+        }
+    }
+}
+
+// Progress is held until the loading card is reached, so the sequence is never cut short
+fn advance_sequence(
+    time: Res<Time>,
+    mut sequences: Query<&mut SplashSequence>,
+    mut cards: Query<(&CardRoot, &mut Visibility)>,
+    mut fades: Query<&mut BackgroundColor, With<Fade>>,
+) -> HiddenProgress {
+    let mut finished = true;
+    for mut sequence in sequences.iter_mut() {
+        sequence.tick(time.delta_seconds());
+        for (card, mut visibility) in cards.iter_mut() {
+            *visibility = if card.0 == sequence.card() {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            };
+        }
+        for mut fade in fades.iter_mut() {
+            fade.0 = Color::BLACK.with_alpha(1.0 - sequence.opacity());
+        }
+        finished &= sequence.finished();
+    }
+    HiddenProgress(finished.into())
+}
+
+fn animate_cassette(
+    accessibility: Res<Accessibility>,
+    sequences: Query<&SplashSequence>,
+    mut cassettes: Query<&mut Style, With<Cassette>>,
+    mut labels: Query<&mut Visibility, With<PlayLabel>>,
+) {
+    for sequence in sequences.iter() {
+        if sequence.card() != SplashCard::TapeInsert {
+            continue;
+        }
+        let inserted = match accessibility.reduce_motion {
+            true => 1.0,
+            false => (sequence.elapsed() / INSERT_SECS).min(1.0),
+        };
+        // ease out, slowing down as it reaches the slot
+        let eased = 1.0 - (1.0 - inserted).powi(3);
+        for mut style in cassettes.iter_mut() {
+            style.top = Val::Percent(100.0 - eased * 50.0);
+        }
+        for mut visibility in labels.iter_mut() {
+            let blink = accessibility.flashing != Flashing::On || sequence.elapsed() % 0.5 < 0.25;
+            *visibility = if inserted >= 1.0 && blink {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            };
+        }
+    }
 }
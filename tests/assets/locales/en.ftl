@@ -0,0 +1 @@
+menu-play = PLAY ▶
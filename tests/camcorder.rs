@@ -0,0 +1,40 @@
+use horror::game::camcorder::Camcorder;
+use horror::game::{Player, MAX_ZOOM};
+use std::f32::consts::FRAC_PI_4;
+
+#[test]
+fn zoom_steps_stay_in_range() {
+    let mut camcorder = Camcorder::default();
+    camcorder.zoom_out();
+    assert_eq!(camcorder.zoom, 1.0);
+
+    for _ in 0..4 {
+        camcorder.zoom_in();
+    }
+    assert!((camcorder.zoom - 2.0).abs() < 0.001);
+
+    for _ in 0..100 {
+        camcorder.zoom_in();
+    }
+    assert_eq!(camcorder.zoom, MAX_ZOOM);
+}
+
+#[test]
+fn zoom_and_night_vision_only_apply_while_raised() {
+    let mut camcorder = Camcorder {
+        zoom: 4.0,
+        night_vision: true,
+        ..Default::default()
+    };
+    let mut player = Player::default();
+    assert_eq!(camcorder.fov(), FRAC_PI_4);
+    assert!(!camcorder.night_vision_on(&player));
+
+    camcorder.raised = true;
+    assert_eq!(camcorder.fov(), FRAC_PI_4 / 4.0);
+    assert!(camcorder.night_vision_on(&player));
+
+    // nothing left to power the infrared light
+    player.battery = 0.0;
+    assert!(!camcorder.night_vision_on(&player));
+}
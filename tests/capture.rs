@@ -0,0 +1,85 @@
+use bevy::prelude::*;
+use bevy::scene::ScenePlugin;
+use bevy_rapier3d::prelude::*;
+use horror::game::capture::{in_frame, unobstructed, Gallery};
+
+#[test]
+fn evidence_counts_once() {
+    let mut gallery = Gallery::default();
+    assert_eq!(gallery.record(vec!["doll".into()]), ["doll"]);
+    assert_eq!(
+        gallery.record(vec!["doll".into(), "grave".into()]),
+        ["grave"]
+    );
+    assert!(gallery.record(Vec::new()).is_empty());
+
+    assert_eq!(gallery.captures.len(), 3);
+    assert_eq!(gallery.found().collect::<Vec<_>>(), ["doll", "grave"]);
+}
+
+#[test]
+fn frame_is_the_view_volume() {
+    assert!(in_frame(Vec3::new(0.0, 0.0, 0.5)));
+    assert!(in_frame(Vec3::new(-1.0, 1.0, 0.1)));
+    assert!(!in_frame(Vec3::new(1.2, 0.0, 0.5)));
+    // behind the camera
+    assert!(!in_frame(Vec3::new(0.0, 0.0, -0.5)));
+}
+
+fn physics() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        TransformPlugin,
+        HierarchyPlugin,
+        AssetPlugin::default(),
+        ScenePlugin,
+        RapierPhysicsPlugin::<NoUserData>::default(),
+    ))
+    .init_asset::<Mesh>();
+    app
+}
+
+fn wall(app: &mut App, sensor: bool) {
+    let mut wall = app.world_mut().spawn((
+        Collider::cuboid(2.0, 2.0, 0.1),
+        TransformBundle::from(Transform::from_xyz(0.0, 0.0, -5.0)),
+    ));
+    if sensor {
+        wall.insert(Sensor);
+    }
+}
+
+fn evidence_seen(app: &mut App) -> bool {
+    let position = Vec3::new(0.0, 0.0, -10.0);
+    let evidence = app
+        .world_mut()
+        .spawn((
+            Collider::ball(0.5),
+            TransformBundle::from(Transform::from_translation(position)),
+        ))
+        .id();
+    let player = app
+        .world_mut()
+        .spawn((Collider::ball(0.5), TransformBundle::default()))
+        .id();
+    app.update();
+    app.update();
+
+    let mut rapier = app.world_mut().resource_mut::<RapierContext>();
+    rapier.update_query_pipeline();
+    unobstructed(&rapier, Vec3::ZERO, evidence, position, player)
+}
+
+#[test]
+fn only_solid_colliders_hide_evidence() {
+    assert!(evidence_seen(&mut physics()));
+
+    let mut app = physics();
+    wall(&mut app, true);
+    assert!(evidence_seen(&mut app), "a trigger volume hid the evidence");
+
+    let mut app = physics();
+    wall(&mut app, false);
+    assert!(!evidence_seen(&mut app));
+}
@@ -0,0 +1,55 @@
+use bevy::prelude::*;
+use bevy::window::WindowMode;
+use clap::Parser;
+use horror::cli::Args;
+
+#[test]
+fn launcher_options_map_to_plugins() {
+    let args = Args::try_parse_from([
+        "horror",
+        "--skip-splash",
+        "--level",
+        "yard",
+        "--seed",
+        "7",
+        "--no-shaders",
+        "--spawn",
+        "-1.5,0,20",
+        "--language",
+        "fr",
+    ])
+    .unwrap();
+    let plugins = args.plugins();
+
+    assert!(!plugins.splash && !plugins.menu && !plugins.shaders);
+    assert_eq!(plugins.level.as_deref(), Some("yard"));
+    assert_eq!(plugins.seed, Some(7));
+    assert_eq!(plugins.spawn, Some(Vec3::new(-1.5, 0.0, 20.0)));
+    assert_eq!(plugins.locale.as_deref(), Some("fr"));
+}
+
+#[test]
+fn window_options() {
+    let window = Args::try_parse_from(["horror", "--fullscreen", "--resolution", "1280x720"])
+        .unwrap()
+        .window();
+    assert_eq!(window.mode, WindowMode::BorderlessFullscreen);
+    assert_eq!(window.resolution.width(), 1280.0);
+    assert_eq!(window.resolution.height(), 720.0);
+
+    // whichever comes last wins
+    let mode = |args: &[&str]| {
+        Args::try_parse_from(["horror"].iter().chain(args))
+            .unwrap()
+            .window()
+            .mode
+    };
+    assert_eq!(mode(&["--fullscreen", "--windowed"]), WindowMode::Windowed);
+    assert_eq!(
+        mode(&["--windowed", "--fullscreen"]),
+        WindowMode::BorderlessFullscreen
+    );
+
+    assert!(Args::try_parse_from(["horror", "--resolution", "1280"]).is_err());
+    assert!(Args::try_parse_from(["horror", "--spawn", "1,2"]).is_err());
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "debug")]
+
+use bevy::prelude::*;
+use horror::console::ConsoleCommands;
+use horror::game::Player;
+
+#[test]
+fn completes_commands_and_arguments() {
+    let commands = ConsoleCommands::builtin();
+
+    assert_eq!(
+        commands.complete("no"),
+        ("noclip ".to_string(), vec!["noclip"])
+    );
+    assert_eq!(commands.complete("state m").0, "state menu ");
+    assert_eq!(commands.complete("flashlight ").0, "flashlight battery ");
+
+    let (completed, candidates) = commands.complete("t");
+    assert_eq!(completed, "t");
+    assert!(candidates.contains(&"tp") && candidates.contains(&"timescale"));
+
+    assert_eq!(commands.complete("tp 1 ").0, "tp 1 ");
+}
+
+#[test]
+fn runs_commands_against_the_world() {
+    let commands = ConsoleCommands::builtin();
+    let mut world = World::new();
+
+    assert!(
+        commands.run(&mut world, "tp 1 2 3").is_err(),
+        "no player yet"
+    );
+    let player = world.spawn((Player::default(), Transform::default())).id();
+
+    commands.run(&mut world, "tp 1 2 -3").unwrap();
+    assert_eq!(
+        world.get::<Transform>(player).unwrap().translation,
+        Vec3::new(1.0, 2.0, -3.0)
+    );
+
+    commands.run(&mut world, "flashlight battery 250").unwrap();
+    assert_eq!(world.get::<Player>(player).unwrap().battery, 100.0);
+
+    assert!(commands.run(&mut world, "tp 1 2").is_err());
+    assert!(commands.run(&mut world, "summon ghost").is_err());
+    assert!(commands.run(&mut world, "help").unwrap().contains("noclip"));
+}
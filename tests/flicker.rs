@@ -0,0 +1,95 @@
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use horror::accessibility::{Accessibility, Flashing};
+use horror::game::g3d::light_flicker;
+use horror::game::Player;
+use horror::rng::GameRng;
+use rand::Rng;
+use std::time::Duration;
+
+// Flashlight intensity over a few seconds at a fixed frame rate
+fn flicker(seed: u64) -> Vec<f32> {
+    flicker_with(seed, Accessibility::default())
+}
+
+fn flicker_with(seed: u64, accessibility: Accessibility) -> Vec<f32> {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            16,
+        )))
+        .insert_resource(GameRng::new(seed))
+        .insert_resource(accessibility)
+        .add_systems(Update, light_flicker);
+    let player = app
+        .world_mut()
+        .spawn((Player::default(), SpotLight::default()))
+        .id();
+
+    (0..1200)
+        .map(|_| {
+            app.update();
+            app.world().get::<SpotLight>(player).unwrap().intensity
+        })
+        .collect()
+}
+
+// Times the flashlight dimmed to `dimmed`
+fn dips(intensities: &[f32], dimmed: f32) -> usize {
+    intensities
+        .windows(2)
+        .filter(|pair| pair[0] != dimmed && pair[1] == dimmed)
+        .count()
+}
+
+#[test]
+fn same_seed_same_flicker() {
+    let intensities = flicker(1);
+    assert_eq!(intensities, flicker(1));
+    assert!(intensities.contains(&50.0), "flashlight never flickered");
+    assert_ne!(intensities, flicker(2));
+}
+
+#[test]
+fn flashing_can_be_limited_or_turned_off() {
+    let limited = flicker_with(
+        1,
+        Accessibility {
+            flashing: Flashing::Limited,
+            ..Default::default()
+        },
+    );
+    assert!(limited.iter().all(|intensity| *intensity >= 150.0));
+    assert!(limited.contains(&150.0));
+    assert!(
+        dips(&limited, 150.0) < dips(&flicker(1), 50.0),
+        "limited flickers as often"
+    );
+
+    let off = flicker_with(
+        1,
+        Accessibility {
+            flashing: Flashing::Off,
+            ..Default::default()
+        },
+    );
+    assert!(off.iter().all(|intensity| *intensity >= 200.0));
+}
+
+#[test]
+fn streams_are_independent() {
+    let mut rng = GameRng::new(3);
+    let flicker: Vec<u32> = (0..10).map(|_| rng.stream("flicker").gen()).collect();
+
+    let mut other = GameRng::new(3);
+    let _: [u32; 4] = other.stream("lightning").gen();
+    let interleaved: Vec<u32> = (0..10).map(|_| other.stream("flicker").gen()).collect();
+    assert_eq!(flicker, interleaved);
+
+    other.reset();
+    assert_eq!(
+        other.stream("flicker").gen::<u32>(),
+        flicker[0],
+        "reset starts streams over"
+    );
+}
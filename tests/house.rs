@@ -0,0 +1,77 @@
+use horror::game::house::{generate, LayoutConfig, PlacementKind};
+
+fn config() -> LayoutConfig {
+    LayoutConfig {
+        pieces: None,
+        rooms: 12,
+        room_size: 6.0,
+        wall_height: 3.0,
+        items: vec!["tape".into(), "key".into()],
+        scares: 3,
+        exit: Some("yard".into()),
+    }
+}
+
+#[test]
+fn same_seed_same_house() {
+    assert_eq!(generate(42, &config()), generate(42, &config()));
+    assert_ne!(generate(42, &config()), generate(43, &config()));
+}
+
+#[test]
+fn rooms_are_connected_by_matching_doors() {
+    for seed in 0..20 {
+        let house = generate(seed, &config());
+        assert_eq!(house.rooms.len(), 12);
+
+        for (index, room) in house.rooms.iter().enumerate() {
+            assert!(
+                house.rooms[..index]
+                    .iter()
+                    .all(|other| other.cell != room.cell),
+                "rooms overlap in seed {seed}"
+            );
+            if index > 0 {
+                assert!(
+                    room.doors.contains(&true),
+                    "room is unreachable in seed {seed}"
+                );
+            }
+            for (side, open) in room.doors.iter().enumerate() {
+                if !open {
+                    continue;
+                }
+                let neighbour = house
+                    .rooms
+                    .iter()
+                    .find(|other| other.cell == room.neighbour(side))
+                    .expect("door leads nowhere");
+                assert!(
+                    neighbour.doors[(side + 2) % 4],
+                    "one way door in seed {seed}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn everything_is_placed_and_the_exit_is_furthest_away() {
+    let house = generate(7, &config());
+    let deepest = house.rooms.iter().map(|room| room.depth).max().unwrap();
+
+    let exits: Vec<_> = house
+        .placements
+        .iter()
+        .filter(|placement| matches!(placement.kind, PlacementKind::Exit(_)))
+        .collect();
+    assert_eq!(exits.len(), 1);
+    assert_eq!(house.rooms[exits[0].room].depth, deepest);
+
+    assert_eq!(house.placements.len(), 1 + 2 + 3);
+    assert!(house
+        .placements
+        .iter()
+        .filter(|placement| !matches!(placement.kind, PlacementKind::Exit(_)))
+        .all(|placement| placement.room != 0));
+}
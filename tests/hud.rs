@@ -0,0 +1,28 @@
+use bevy::prelude::*;
+use horror::game::g2d::{zoom_level, ViewfinderLayout, REFERENCE_HEIGHT};
+use std::f32::consts::FRAC_PI_4;
+
+#[test]
+fn viewfinder_fills_the_window_up_to_widescreen() {
+    let layout = ViewfinderLayout::fit(Vec2::new(1280.0, REFERENCE_HEIGHT));
+    assert_eq!(layout.position, Vec2::ZERO);
+    assert_eq!(layout.size, Vec2::new(1280.0, 720.0));
+    assert_eq!(layout.scale, 1.0);
+
+    // 4:3 uses the whole window
+    let layout = ViewfinderLayout::fit(Vec2::new(1024.0, 768.0));
+    assert_eq!(layout.size, Vec2::new(1024.0, 768.0));
+
+    // ultrawide is centred at 16:9
+    let layout = ViewfinderLayout::fit(Vec2::new(3440.0, 1440.0));
+    assert!(layout.size.abs_diff_eq(Vec2::new(2560.0, 1440.0), 0.01));
+    assert!(layout.position.abs_diff_eq(Vec2::new(440.0, 0.0), 0.01));
+    assert_eq!(layout.scale, 2.0);
+}
+
+#[test]
+fn zoom_follows_field_of_view() {
+    let projection = |fov| Projection::Perspective(PerspectiveProjection { fov, ..default() });
+    assert_eq!(zoom_level(&projection(FRAC_PI_4)), 1.0);
+    assert_eq!(zoom_level(&projection(FRAC_PI_4 / 4.0)), 4.0);
+}
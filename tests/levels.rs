@@ -0,0 +1,145 @@
+use bevy::gltf::Gltf;
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+use bevy_rapier3d::prelude::*;
+use horror::assets::{LevelAssets, LoadingProgress};
+use horror::game::level::{
+    ChangeLevel, CurrentLevel, LevelFade, LevelPlugin, LevelRegistry, LevelTransition,
+    TransitionPhase, LEVEL_COLLECTION,
+};
+use horror::rng::GameRng;
+use horror::{GameState, StatePlugin};
+use std::time::Duration;
+
+#[test]
+fn registry_parses_and_exits_lead_to_known_levels() {
+    let registry: LevelRegistry =
+        ron::from_str(include_str!("../assets/horror.levels.ron")).unwrap();
+
+    assert!(!registry.levels.is_empty());
+    for level in &registry.levels {
+        let layout_exit = level
+            .layout
+            .as_ref()
+            .and_then(|layout| layout.exit.as_ref());
+        for to in level.exits.iter().map(|exit| &exit.to).chain(layout_exit) {
+            assert!(
+                registry.get(to).is_some(),
+                "exit in {} leads to unknown level {to}",
+                level.name,
+            );
+        }
+    }
+}
+
+// A level without assets to start in, and one whose world never loads
+fn app(plugin: LevelPlugin) -> App {
+    let mut registry = LevelRegistry::fallback();
+    let mut missing = registry.levels[0].clone();
+    missing.name = "missing".into();
+    missing.world = Some("models/missing.glb".into());
+    registry.levels.push(missing);
+
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        StatesPlugin,
+        StatePlugin,
+        plugin,
+    ))
+    .init_asset::<Mesh>()
+    .init_asset::<StandardMaterial>()
+    .init_asset::<Image>()
+    .init_asset::<Gltf>()
+    .init_asset::<AudioSource>()
+    .init_asset::<LevelRegistry>()
+    .add_event::<CollisionEvent>()
+    .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+        100,
+    )))
+    .insert_resource(GameRng::new(0))
+    .init_resource::<LoadingProgress>();
+    let registry = app
+        .world_mut()
+        .resource_mut::<Assets<LevelRegistry>>()
+        .add(registry);
+    app.insert_resource(LevelAssets { registry });
+    app
+}
+
+fn phase(app: &App) -> Option<&'static str> {
+    let transition = app.world().get_resource::<LevelTransition>()?;
+    Some(match transition.phase() {
+        TransitionPhase::FadeOut => "fade out",
+        TransitionPhase::Loading(_) => "loading",
+        TransitionPhase::FadeIn => "fade in",
+    })
+}
+
+// Every phase the transition goes through, once each, until it is over or the game is left
+fn phases(app: &mut App) -> Vec<&'static str> {
+    let mut phases = Vec::new();
+    for _ in 0..100 {
+        app.update();
+        if *app.world().resource::<State<GameState>>().get() != GameState::Game {
+            break;
+        }
+        match phase(app) {
+            Some(phase) if phases.last() != Some(&phase) => phases.push(phase),
+            Some(_) => {}
+            None => break,
+        }
+    }
+    phases
+}
+
+#[test]
+fn transition_fades_out_loads_and_fades_in() {
+    let mut app = app(LevelPlugin::default());
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Game);
+    // the start level is already covered, and has nothing to load
+    assert_eq!(phases(&mut app), ["fade in"]);
+
+    app.world_mut().send_event(ChangeLevel("missing".into()));
+    let phases = phases(&mut app);
+    assert_eq!(phases[..2], ["fade out", "loading"]);
+
+    if cfg!(debug_assertions) {
+        // a placeholder stands in for the world
+        assert_eq!(phases[2..], ["fade in"]);
+    } else {
+        assert_eq!(
+            *app.world().resource::<State<GameState>>().get(),
+            GameState::LoadFailed
+        );
+        let progress = app.world().resource::<LoadingProgress>();
+        assert!(progress.collections.iter().any(
+            |collection| collection.name == LEVEL_COLLECTION && !collection.failures.is_empty()
+        ));
+    }
+}
+
+#[test]
+fn unknown_start_level_falls_back_to_the_first() {
+    let mut app = app(LevelPlugin {
+        level: Some("typo".into()),
+        ..Default::default()
+    });
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Game);
+    assert_eq!(phases(&mut app), ["fade in"]);
+
+    assert_eq!(app.world().resource::<CurrentLevel>().0, "fallback");
+    // the cover is lifted
+    let cover = app
+        .world_mut()
+        .query_filtered::<&BackgroundColor, With<LevelFade>>()
+        .single(app.world())
+        .0;
+    assert_eq!(cover.alpha(), 0.0);
+}
@@ -0,0 +1,99 @@
+use bevy::prelude::*;
+use bevy::text::FontLoader;
+use bevy_egui::{egui, EguiUserTextures};
+use horror::localization::{
+    FontBytes, LocaleRegistry, Localization, LocalizationPlugin, FALLBACK_LOCALE,
+};
+use std::time::Duration;
+
+const EN: &str = include_str!("../assets/locales/en.ftl");
+
+fn keys(source: &str) -> impl Iterator<Item = &str> {
+    source
+        .lines()
+        .filter(|line| line.starts_with(|c: char| c.is_ascii_lowercase()))
+        .filter_map(|line| line.split_once(" =").map(|(key, _)| key))
+}
+
+#[test]
+fn every_locale_translates_every_key() {
+    let registry: LocaleRegistry =
+        ron::from_str(include_str!("../assets/horror.locales.ron")).unwrap();
+    assert!(registry
+        .locales
+        .iter()
+        .any(|locale| locale.id == FALLBACK_LOCALE));
+
+    for locale in &registry.locales {
+        let path = format!("{}/assets/{}", env!("CARGO_MANIFEST_DIR"), locale.file);
+        let source = std::fs::read_to_string(&path).unwrap();
+        let localization = Localization::from_sources([(locale.id.as_str(), source.as_str())]);
+        for key in keys(EN) {
+            assert_ne!(
+                localization.text(key),
+                key,
+                "{} is missing {key}",
+                locale.id
+            );
+        }
+    }
+}
+
+#[test]
+fn missing_strings_fall_back() {
+    let localization = Localization::from_sources([
+        ("fr", "menu-play = LECTURE ▶"),
+        (FALLBACK_LOCALE, "menu-play = PLAY ▶\nmenu-quit = STOP ■"),
+    ]);
+
+    assert_eq!(localization.text("menu-play"), "LECTURE ▶");
+    assert_eq!(localization.text("menu-quit"), "STOP ■");
+    // nothing has it, so at least show which string is missing
+    assert_eq!(localization.text("menu-level"), "menu-level");
+}
+
+#[test]
+fn locale_fonts_load_for_bevy_ui_and_egui() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin {
+            file_path: "tests/assets".into(),
+            ..default()
+        },
+        LocalizationPlugin {
+            registry: "fonts.locales.ron".into(),
+            locale: Some("el".into()),
+        },
+    ))
+    // usually from `TextPlugin` and `EguiPlugin`
+    .init_asset::<Font>()
+    .init_asset_loader::<FontLoader>()
+    .init_resource::<EguiUserTextures>();
+
+    let loaded = |app: &App| {
+        let localization = app.world().resource::<Localization>();
+        let fonts = app.world().resource::<Assets<Font>>();
+        localization.font().is_some_and(|font| fonts.contains(font))
+            && localization
+                .egui_fonts(app.world().resource::<Assets<FontBytes>>())
+                .font_data
+                .contains_key("locale")
+    };
+    for _ in 0..500 {
+        app.update();
+        if loaded(&app) {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    assert!(loaded(&app), "locale font never loaded");
+
+    let localization = app.world().resource::<Localization>();
+    assert_eq!(localization.text("menu-play"), "ΑΝΑΠΑΡΑΓΩΓΗ ▶");
+    let fonts = localization.egui_fonts(app.world().resource::<Assets<FontBytes>>());
+    for family in [egui::FontFamily::Proportional, egui::FontFamily::Monospace] {
+        // after egui's own fonts, only filling in what they're missing
+        assert_eq!(fonts.families[&family].last().unwrap(), "locale");
+    }
+}
@@ -0,0 +1,53 @@
+use bevy_egui::egui;
+use horror::navigation::{MenuAction, MenuCue, MenuFocus};
+use leafwing_input_manager::prelude::*;
+
+// Lay out a menu of three buttons for one frame, returning which were pressed
+fn frame(ctx: &egui::Context, focus: &mut MenuFocus, pressed: &[MenuAction]) -> Vec<bool> {
+    let mut actions = ActionState::<MenuAction>::default();
+    for action in pressed {
+        actions.press(action);
+    }
+    focus.begin("test", &actions);
+
+    let mut buttons = Vec::new();
+    let _ = ctx.run(egui::RawInput::default(), |ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            for label in ["Play", "Settings", "Quit"] {
+                buttons.push(focus.button(ui, egui::RichText::new(label)));
+            }
+        });
+    });
+    buttons
+}
+
+#[test]
+fn focus_moves_and_wraps() {
+    let ctx = egui::Context::default();
+    let mut focus = MenuFocus::default();
+
+    frame(&ctx, &mut focus, &[]);
+    assert_eq!(focus.index(), 0);
+
+    frame(&ctx, &mut focus, &[MenuAction::Up]);
+    assert_eq!(
+        focus.index(),
+        2,
+        "up from the first entry wraps to the last"
+    );
+
+    frame(&ctx, &mut focus, &[MenuAction::Down]);
+    frame(&ctx, &mut focus, &[MenuAction::Down]);
+    assert_eq!(focus.index(), 1);
+
+    assert_eq!(
+        frame(&ctx, &mut focus, &[MenuAction::Confirm]),
+        [false, true, false]
+    );
+    assert_eq!(focus.take_cue(), Some(MenuCue::Confirm));
+    assert_eq!(focus.take_cue(), None);
+
+    let actions = ActionState::<MenuAction>::default();
+    focus.begin("another screen", &actions);
+    assert_eq!(focus.index(), 0, "focus starts over on a new screen");
+}
@@ -0,0 +1,97 @@
+use horror::game::level::LevelRegistry;
+use horror::game::objectives::{
+    ObjectiveChanged, ObjectiveConfig, ObjectiveEvent, ObjectiveState, Objectives,
+};
+
+fn configs() -> Vec<ObjectiveConfig> {
+    ron::from_str(
+        r#"[
+            (id: "doll", complete: EvidenceCaptured("doll")),
+            (id: "key", complete: ItemPickedUp("key"), fail: Some(TriggerEntered("scare"))),
+            (id: "yard", complete: ExitReached("yard"), after: ["doll"]),
+        ]"#,
+    )
+    .unwrap()
+}
+
+fn changed(id: &str, state: ObjectiveState) -> ObjectiveChanged {
+    ObjectiveChanged {
+        id: id.into(),
+        state,
+    }
+}
+
+#[test]
+fn completing_reveals_what_comes_after() {
+    let (mut objectives, changes) = Objectives::new(&configs());
+    assert_eq!(
+        changes,
+        [
+            changed("doll", ObjectiveState::Active),
+            changed("key", ObjectiveState::Active),
+        ]
+    );
+    assert_eq!(
+        objectives.get("yard").unwrap().state,
+        ObjectiveState::Hidden
+    );
+
+    assert_eq!(
+        objectives.handle(&ObjectiveEvent::EvidenceCaptured("doll".into())),
+        [
+            changed("doll", ObjectiveState::Completed),
+            changed("yard", ObjectiveState::Active),
+        ]
+    );
+    assert_eq!(
+        objectives.handle(&ObjectiveEvent::ExitReached("yard".into())),
+        [changed("yard", ObjectiveState::Completed)]
+    );
+    // already completed
+    assert!(objectives
+        .handle(&ObjectiveEvent::EvidenceCaptured("doll".into()))
+        .is_empty());
+}
+
+#[test]
+fn failing_is_final() {
+    let (mut objectives, _) = Objectives::new(&configs());
+    assert_eq!(
+        objectives.handle(&ObjectiveEvent::TriggerEntered("scare".into())),
+        [changed("key", ObjectiveState::Failed)]
+    );
+    assert!(objectives
+        .handle(&ObjectiveEvent::ItemPickedUp("key".into()))
+        .is_empty());
+    assert_eq!(objectives.get("key").unwrap().state, ObjectiveState::Failed);
+}
+
+#[test]
+fn hidden_objectives_do_not_progress() {
+    let (mut objectives, _) = Objectives::new(&configs());
+    assert!(objectives
+        .handle(&ObjectiveEvent::ExitReached("yard".into()))
+        .is_empty());
+    assert_eq!(
+        objectives.get("yard").unwrap().state,
+        ObjectiveState::Hidden
+    );
+}
+
+#[test]
+fn registry_objectives_come_after_known_objectives() {
+    let registry: LevelRegistry =
+        ron::from_str(include_str!("../assets/horror.levels.ron")).unwrap();
+
+    let (objectives, changes) = Objectives::new(&registry.objectives);
+    assert!(!changes.is_empty());
+    for objective in &objectives.0 {
+        for id in &objective.config.after {
+            assert!(
+                objectives.get(id).is_some(),
+                "{} comes after unknown objective {id}",
+                objective.config.id,
+            );
+        }
+    }
+}
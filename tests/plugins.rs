@@ -0,0 +1,72 @@
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy_egui::EguiUserTextures;
+use horror::assets::AssetsPlugin;
+use horror::game::camcorder::CamcorderPlugin;
+use horror::game::capture::CapturePlugin;
+use horror::game::g3d::G3dPlugin;
+use horror::game::level::LevelPlugin;
+use horror::game::objectives::ObjectivesPlugin;
+use horror::game::GameplayState;
+use horror::localization::{Locale, Localization, LocalizationPlugin};
+use horror::skybox::SkyboxPlugin;
+use horror::{GameState, HorrorPlugins};
+
+#[test]
+fn objectives_and_captures_can_be_left_out() {
+    let plugins = HorrorPlugins {
+        menu: false,
+        splash: false,
+        egui: false,
+        shaders: false,
+        ..default()
+    }
+    .build()
+    .disable::<ObjectivesPlugin>()
+    .disable::<CapturePlugin>()
+    // these need real assets, a renderer or physics
+    .disable::<AssetsPlugin>()
+    .disable::<SkyboxPlugin>()
+    .disable::<LocalizationPlugin>()
+    .disable::<G3dPlugin>()
+    .disable::<CamcorderPlugin>()
+    .disable::<LevelPlugin>();
+    #[cfg(feature = "debug")]
+    let plugins = plugins
+        .disable::<horror::debug::DebugPlugin>()
+        .disable::<horror::console::ConsolePlugin>()
+        .disable::<horror::perf::PerfPlugin>()
+        .disable::<horror::game::debug3d::Debug3DPlugin>();
+
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        StatesPlugin,
+        InputPlugin,
+        WindowPlugin::default(),
+        AssetPlugin::default(),
+        AudioPlugin::default(),
+        plugins,
+    ))
+    .init_resource::<EguiUserTextures>()
+    .init_resource::<Localization>()
+    .insert_resource(Locale("en".into()));
+
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Game);
+    app.update();
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<NextState<GameplayState>>()
+        .set(GameplayState::Paused);
+    app.update();
+    app.update();
+
+    assert_eq!(
+        app.world().resource::<State<GameplayState>>().get(),
+        &GameplayState::Paused
+    );
+}
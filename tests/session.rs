@@ -0,0 +1,129 @@
+use bevy::gltf::Gltf;
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::scene::ScenePlugin;
+use bevy::state::app::StatesPlugin;
+use horror::accessibility::Accessibility;
+use horror::assets::{AudioAssets, LevelAssets, LoadingProgress};
+use horror::game::g3d::G3dPlugin;
+use horror::game::level::{LevelPlugin, LevelRegistry};
+use horror::game::{Action, GamePlugin, GameSession, GameplayState};
+use horror::rng::GameRng;
+use horror::{GameState, StatePlugin};
+use leafwing_input_manager::prelude::*;
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        StatesPlugin,
+        InputPlugin,
+        StatePlugin,
+        GamePlugin,
+    ));
+    app
+}
+
+fn set_state(app: &mut App, state: GameState) {
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(state);
+    app.update();
+}
+
+fn count<C: Component>(app: &mut App) -> usize {
+    app.world_mut()
+        .query_filtered::<(), With<C>>()
+        .iter(app.world())
+        .count()
+}
+
+#[test]
+fn session_is_torn_down_on_exit() {
+    let mut app = app();
+
+    for _ in 0..2 {
+        set_state(&mut app, GameState::Game);
+        assert_eq!(count::<ActionState<Action>>(&mut app), 1);
+
+        app.world_mut()
+            .spawn((Name::new("leftover"), GameSession))
+            .with_children(|parent| {
+                parent.spawn(Name::new("leftover child"));
+            });
+        app.insert_resource(AmbientLight {
+            color: Color::BLACK,
+            brightness: 0.0,
+        });
+        app.world_mut()
+            .resource_mut::<NextState<GameplayState>>()
+            .set(GameplayState::Paused);
+        app.update();
+        assert!(app.world().resource::<Time<Virtual>>().is_paused());
+
+        set_state(&mut app, GameState::Menu);
+        app.update();
+
+        assert_eq!(count::<GameSession>(&mut app), 0);
+        assert_eq!(count::<ActionState<Action>>(&mut app), 0);
+        assert_eq!(count::<Name>(&mut app), 0);
+        assert_eq!(
+            app.world().resource::<AmbientLight>().brightness,
+            AmbientLight::default().brightness
+        );
+        assert_eq!(
+            app.world().resource::<State<GameplayState>>().get(),
+            &GameplayState::Playing
+        );
+        assert!(!app.world().resource::<Time<Virtual>>().is_paused());
+    }
+}
+
+// With the player and the level spawned by their own plugins rather than by hand
+fn world_app() -> App {
+    let mut app = app();
+    app.add_plugins((
+        TransformPlugin,
+        HierarchyPlugin,
+        AssetPlugin::default(),
+        ScenePlugin,
+        G3dPlugin,
+        LevelPlugin::default(),
+    ))
+    .init_asset::<Mesh>()
+    .init_asset::<StandardMaterial>()
+    .init_asset::<Image>()
+    .init_asset::<Gltf>()
+    .init_asset::<AudioSource>()
+    .init_asset::<LevelRegistry>()
+    .init_resource::<Accessibility>()
+    .init_resource::<LoadingProgress>()
+    .insert_resource(GameRng::new(0))
+    .insert_resource(AudioAssets {
+        intro: Handle::default(),
+    });
+    let registry = app
+        .world_mut()
+        .resource_mut::<Assets<LevelRegistry>>()
+        .add(LevelRegistry::fallback());
+    app.insert_resource(LevelAssets { registry });
+    app
+}
+
+#[test]
+fn moon_and_listener_are_torn_down_on_exit() {
+    let mut app = world_app();
+
+    for _ in 0..2 {
+        set_state(&mut app, GameState::Game);
+        app.update();
+        assert_eq!(count::<DirectionalLight>(&mut app), 1);
+        assert_eq!(count::<SpatialListener>(&mut app), 1);
+
+        set_state(&mut app, GameState::Menu);
+        app.update();
+        assert_eq!(count::<DirectionalLight>(&mut app), 0);
+        assert_eq!(count::<SpatialListener>(&mut app), 0);
+        assert_eq!(count::<Name>(&mut app), 0);
+    }
+}
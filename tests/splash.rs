@@ -0,0 +1,37 @@
+use horror::splash::{SplashCard, SplashSequence};
+
+#[test]
+fn sequence_runs_through_every_card() {
+    let mut sequence = SplashSequence::default();
+    assert_eq!(sequence.card(), SplashCard::Studio);
+    assert_eq!(sequence.opacity(), 0.0, "cards fade in");
+
+    for (card, duration) in SplashCard::SEQUENCE {
+        assert_eq!(sequence.card(), card);
+        sequence.tick(duration / 2.0);
+        assert_eq!(sequence.opacity(), 1.0);
+        sequence.tick(duration / 2.0);
+    }
+    assert!(sequence.finished());
+
+    // loading stays up for as long as it takes
+    sequence.tick(60.0);
+    assert_eq!(sequence.card(), SplashCard::Loading);
+}
+
+#[test]
+fn skipping_goes_straight_to_loading() {
+    let mut sequence = SplashSequence::default();
+    sequence.tick(0.1);
+    sequence.skip();
+    assert!(sequence.finished());
+    assert_eq!(sequence.elapsed(), 0.0);
+
+    sequence.tick(1.0);
+    sequence.skip();
+    assert_eq!(
+        sequence.elapsed(),
+        1.0,
+        "skipping again doesn't restart loading"
+    );
+}
@@ -0,0 +1,155 @@
+#![cfg(feature = "shaders")]
+
+use bevy::prelude::*;
+use horror::accessibility::{Accessibility, Flashing};
+use horror::game::camcorder::Camcorder;
+use horror::game::vhs::{
+    apply_preset, update_grain, update_night_vision, NightVisionFilter, TapeGrain, VHSShader,
+    VhsPreset, NIGHT_VISION_TINT,
+};
+use horror::game::Player;
+
+struct Tape {
+    app: App,
+    shader: Handle<VHSShader>,
+    grain: Handle<TapeGrain>,
+}
+
+impl Tape {
+    fn new() -> Self {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<VHSShader>()
+            .init_asset::<TapeGrain>()
+            .init_resource::<Accessibility>()
+            .init_resource::<VhsPreset>()
+            .add_systems(Update, (apply_preset, update_grain));
+
+        let shader = app
+            .world_mut()
+            .resource_mut::<Assets<VHSShader>>()
+            .add(VHSShader {
+                color: LinearRgba::WHITE,
+                img: Handle::default(),
+                noise: 1.0,
+            });
+        let grain = app
+            .world_mut()
+            .resource_mut::<Assets<TapeGrain>>()
+            .add(TapeGrain::default());
+        Self { app, shader, grain }
+    }
+
+    fn shader(&self) -> &VHSShader {
+        self.app
+            .world()
+            .resource::<Assets<VHSShader>>()
+            .get(&self.shader)
+            .unwrap()
+    }
+
+    fn grain(&self) -> &TapeGrain {
+        self.app
+            .world()
+            .resource::<Assets<TapeGrain>>()
+            .get(&self.grain)
+            .unwrap()
+    }
+}
+
+#[test]
+fn presets_tint_the_tape_and_the_grain_over_the_game() {
+    let mut tape = Tape::new();
+
+    for preset in VhsPreset::ALL {
+        *tape.app.world_mut().resource_mut::<VhsPreset>() = preset;
+        tape.app.update();
+
+        assert_eq!(tape.shader().color, preset.tint(), "{}", preset.name());
+        assert_eq!(tape.grain().tint, preset.tint(), "{}", preset.name());
+        assert_eq!(
+            tape.grain().strength,
+            0.05 * preset.wear(),
+            "{}",
+            preset.name()
+        );
+    }
+
+    for (i, preset) in VhsPreset::ALL.iter().enumerate() {
+        for other in &VhsPreset::ALL[i + 1..] {
+            assert_ne!(
+                preset.tint(),
+                other.tint(),
+                "{} and {} look the same",
+                preset.name(),
+                other.name(),
+            );
+        }
+    }
+}
+
+#[test]
+fn no_flashing_stills_the_tape() {
+    let mut tape = Tape::new();
+    tape.app
+        .world_mut()
+        .resource_mut::<Accessibility>()
+        .flashing = Flashing::Off;
+    tape.app.update();
+
+    assert_eq!(tape.shader().noise, 0.0);
+    assert_eq!(tape.grain().strength, 0.0);
+}
+
+#[test]
+fn night_vision_turns_the_view_green() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+        .init_asset::<NightVisionFilter>()
+        .add_systems(Update, update_night_vision);
+    let filter = app
+        .world_mut()
+        .resource_mut::<Assets<NightVisionFilter>>()
+        .add(NightVisionFilter::default());
+    let tint = |app: &App| {
+        app.world()
+            .resource::<Assets<NightVisionFilter>>()
+            .get(&filter)
+            .unwrap()
+            .tint
+    };
+
+    let player = app
+        .world_mut()
+        .spawn((
+            Camcorder {
+                raised: true,
+                night_vision: true,
+                ..Default::default()
+            },
+            Player::default(),
+        ))
+        .id();
+    app.update();
+    assert_eq!(tint(&app), NIGHT_VISION_TINT);
+
+    // the infrared light is out, so is the picture it made
+    app.world_mut().get_mut::<Player>(player).unwrap().battery = 0.0;
+    app.update();
+    assert_eq!(tint(&app), LinearRgba::WHITE);
+}
+
+#[cfg(feature = "debug")]
+#[test]
+fn vhs_command_changes_the_preset() {
+    use horror::console::ConsoleCommands;
+
+    let mut tape = Tape::new();
+    ConsoleCommands::builtin()
+        .run(tape.app.world_mut(), "vhs damaged")
+        .unwrap();
+    tape.app.update();
+
+    assert_eq!(tape.shader().color, VhsPreset::Damaged.tint());
+    assert_eq!(tape.grain().tint, VhsPreset::Damaged.tint());
+}